@@ -6,10 +6,10 @@ use serde::{Deserialize, Serialize};
 use std::{
     net::TcpListener,
     process::Command,
-    sync::{atomic::{AtomicU64, Ordering}, Mutex},
+    sync::{atomic::{AtomicBool, AtomicU64, Ordering}, Mutex},
     time::Duration,
 };
-use std::{collections::{HashMap, HashSet}, fs, path::{Path, PathBuf}};
+use std::{collections::{HashMap, HashSet, VecDeque}, fs, path::{Path, PathBuf}};
 use std::env;
 use std::time::{SystemTime, UNIX_EPOCH};
 use tauri::{Emitter, Manager, WebviewUrl, WebviewWindowBuilder};
@@ -18,6 +18,16 @@ use tauri::utils::config::BackgroundThrottlingPolicy;
 /// Global counter for generating unique window labels.
 static WINDOW_COUNTER: AtomicU64 = AtomicU64::new(1);
 
+/// Set once the app starts shutting down so window teardown doesn't overwrite
+/// the persisted window session.
+static APP_EXITING: AtomicBool = AtomicBool::new(false);
+
+/// Set once the user confirmed quitting, so the retried last-window close goes through.
+static QUIT_CONFIRMED: AtomicBool = AtomicBool::new(false);
+
+/// When `open_new_window` last created a window, used to coalesce rapid triggers.
+static LAST_NEW_WINDOW_AT: Mutex<Option<std::time::Instant>> = Mutex::new(None);
+
 fn next_window_label() -> String {
     let n = WINDOW_COUNTER.fetch_add(1, Ordering::Relaxed);
     if n == 1 {
@@ -62,6 +72,42 @@ fn dispatch_menu_action<R: tauri::Runtime>(app: &tauri::AppHandle<R>, action: &s
     eval_in_focused_window(app, &script);
 }
 
+fn dispatch_host_auth_required<R: tauri::Runtime>(window: &tauri::Webview<R>, host_url: &str) {
+    let _ = window.emit_to(window.label(), "openchamber:host-auth-required", host_url);
+
+    let event = serde_json::to_string("openchamber:host-auth-required")
+        .unwrap_or_else(|_| "\"openchamber:host-auth-required\"".into());
+    let detail = serde_json::to_string(&serde_json::json!({ "url": host_url })).unwrap_or_else(|_| "{}".into());
+    let script = format!("window.dispatchEvent(new CustomEvent({event}, {{ detail: {detail} }}));");
+    let _ = window.eval(&script);
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct WindowLifecyclePayload {
+    label: String,
+    window_count: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    focused: Option<bool>,
+}
+
+/// Emit `openchamber:window-opened` / `-closed` / `-focus-changed` to all windows.
+fn emit_window_lifecycle<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    event: &str,
+    label: &str,
+    focused: Option<bool>,
+) {
+    let _ = app.emit(
+        event,
+        WindowLifecyclePayload {
+            label: label.to_string(),
+            window_count: app.webview_windows().len(),
+            focused,
+        },
+    );
+}
+
 fn dispatch_check_for_updates<R: tauri::Runtime>(app: &tauri::AppHandle<R>) {
     let _ = app.emit("openchamber:check-for-updates", ());
 
@@ -118,6 +164,93 @@ const MENU_ITEM_REQUEST_FEATURE_ID: &str = "menu_request_feature";
 #[cfg(target_os = "macos")]
 const MENU_ITEM_JOIN_DISCORD_ID: &str = "menu_join_discord";
 
+/// Remappable menu actions: (settings key, menu item id, default accelerator).
+/// New Session/New Worktree are left out because auto-worktree mode swaps them.
+#[cfg(target_os = "macos")]
+const MENU_SHORTCUT_ACTIONS: &[(&str, &str, Option<&str>)] = &[
+    ("settings", MENU_ITEM_SETTINGS_ID, Some("Cmd+,")),
+    ("commandPalette", MENU_ITEM_COMMAND_PALETTE_ID, Some("Cmd+K")),
+    ("newWindow", MENU_ITEM_NEW_WINDOW_ID, Some("Cmd+Shift+Alt+N")),
+    ("openGitTab", MENU_ITEM_OPEN_GIT_TAB_ID, Some("Cmd+G")),
+    ("openDiffTab", MENU_ITEM_OPEN_DIFF_TAB_ID, Some("Cmd+E")),
+    ("openFilesTab", MENU_ITEM_OPEN_FILES_TAB_ID, None),
+    ("openTerminalTab", MENU_ITEM_OPEN_TERMINAL_TAB_ID, Some("Cmd+T")),
+    ("toggleSidebar", MENU_ITEM_TOGGLE_SIDEBAR_ID, Some("Cmd+L")),
+    ("toggleMemoryDebug", MENU_ITEM_TOGGLE_MEMORY_DEBUG_ID, Some("Cmd+Shift+D")),
+    ("helpDialog", MENU_ITEM_HELP_DIALOG_ID, Some("Cmd+.")),
+    ("downloadLogs", MENU_ITEM_DOWNLOAD_LOGS_ID, Some("Cmd+Shift+L")),
+];
+
+/// Accelerators owned by fixed or predefined menu items, never assignable.
+#[cfg(target_os = "macos")]
+const RESERVED_MENU_SHORTCUTS: &[&str] = &[
+    "Cmd+N", "Cmd+Shift+N", "Cmd+Q", "Cmd+W", "Cmd+H", "Cmd+Alt+H", "Cmd+M", "Cmd+Z",
+    "Cmd+Shift+Z", "Cmd+X", "Cmd+C", "Cmd+V", "Cmd+A", "Cmd+Ctrl+F",
+];
+
+/// Canonical form of an accelerator for conflict checks: modifier aliases folded,
+/// modifiers sorted, key uppercased ("shift+cmd+k" == "Cmd+Shift+K").
+#[cfg(target_os = "macos")]
+fn normalize_accelerator(raw: &str) -> Option<String> {
+    let mut modifiers: Vec<&str> = Vec::new();
+    let mut key: Option<String> = None;
+    for part in raw.split('+').map(str::trim) {
+        let modifier = match part.to_ascii_lowercase().as_str() {
+            "cmd" | "command" | "super" | "meta" | "cmdorctrl" | "commandorcontrol" => Some("Cmd"),
+            "alt" | "option" => Some("Alt"),
+            "ctrl" | "control" => Some("Ctrl"),
+            "shift" => Some("Shift"),
+            _ => None,
+        };
+        match modifier {
+            Some(modifier) if !modifiers.contains(&modifier) => modifiers.push(modifier),
+            Some(_) => return None,
+            None if part.is_empty() || key.is_some() => return None,
+            None => key = Some(part.to_ascii_uppercase()),
+        }
+    }
+    modifiers.sort_unstable();
+    let key = key?;
+    Some(modifiers.into_iter().chain(std::iter::once(key.as_str())).collect::<Vec<_>>().join("+"))
+}
+
+/// User overrides from `desktopMenuShortcuts` (action -> accelerator, `null` to unbind).
+#[cfg(target_os = "macos")]
+fn read_menu_shortcut_overrides_from_disk() -> HashMap<String, Option<String>> {
+    read_settings_value("desktopMenuShortcuts")
+        .as_ref()
+        .and_then(|v| v.as_object())
+        .map(|entries| {
+            entries
+                .iter()
+                .filter_map(|(action, value)| match value {
+                    serde_json::Value::Null => Some((action.clone(), None)),
+                    serde_json::Value::String(accel) if !accel.trim().is_empty() => {
+                        Some((action.clone(), Some(accel.trim().to_string())))
+                    }
+                    _ => None,
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Effective accelerator per remappable action: override, else the default.
+#[cfg(target_os = "macos")]
+fn resolved_menu_shortcuts() -> HashMap<&'static str, Option<String>> {
+    let overrides = read_menu_shortcut_overrides_from_disk();
+    MENU_SHORTCUT_ACTIONS
+        .iter()
+        .map(|(action, _, default)| {
+            let accel = match overrides.get(*action) {
+                Some(value) => value.clone(),
+                None => default.map(str::to_string),
+            };
+            (*action, accel)
+        })
+        .collect()
+}
+
 #[cfg(target_os = "macos")]
 const GITHUB_BUG_REPORT_URL: &str =
     "https://github.com/btriapitsyn/openchamber/issues/new?template=bug_report.yml";
@@ -145,6 +278,9 @@ fn build_macos_menu<R: tauri::Runtime>(
     let new_session_shortcut = if auto_worktree { "Cmd+Shift+N" } else { "Cmd+N" };
     let new_worktree_shortcut = if auto_worktree { "Cmd+N" } else { "Cmd+Shift+N" };
 
+    let shortcuts = resolved_menu_shortcuts();
+    let shortcut = |action: &str| shortcuts.get(action).cloned().flatten();
+
     let about = MenuItem::with_id(
         app,
         MENU_ITEM_ABOUT_ID,
@@ -157,18 +293,19 @@ fn build_macos_menu<R: tauri::Runtime>(
         app,
         MENU_ITEM_CHECK_FOR_UPDATES_ID,
         "Check for Updates",
-        true,
+        !is_updater_disabled(),
         None::<&str>,
     )?;
 
-    let settings = MenuItem::with_id(app, MENU_ITEM_SETTINGS_ID, "Settings", true, Some("Cmd+,"))?;
+    let settings =
+        MenuItem::with_id(app, MENU_ITEM_SETTINGS_ID, "Settings", true, shortcut("settings"))?;
 
     let command_palette = MenuItem::with_id(
         app,
         MENU_ITEM_COMMAND_PALETTE_ID,
         "Command Palette",
         true,
-        Some("Cmd+K"),
+        shortcut("commandPalette"),
     )?;
 
     let new_window = MenuItem::with_id(
@@ -176,7 +313,7 @@ fn build_macos_menu<R: tauri::Runtime>(
         MENU_ITEM_NEW_WINDOW_ID,
         "New Window",
         true,
-        Some("Cmd+Shift+Alt+N"),
+        shortcut("newWindow"),
     )?;
 
     let new_session = MenuItem::with_id(
@@ -204,17 +341,17 @@ fn build_macos_menu<R: tauri::Runtime>(
     )?;
 
     let open_git_tab =
-        MenuItem::with_id(app, MENU_ITEM_OPEN_GIT_TAB_ID, "Git", true, Some("Cmd+G"))?;
+        MenuItem::with_id(app, MENU_ITEM_OPEN_GIT_TAB_ID, "Git", true, shortcut("openGitTab"))?;
     let open_diff_tab =
-        MenuItem::with_id(app, MENU_ITEM_OPEN_DIFF_TAB_ID, "Diff", true, Some("Cmd+E"))?;
+        MenuItem::with_id(app, MENU_ITEM_OPEN_DIFF_TAB_ID, "Diff", true, shortcut("openDiffTab"))?;
     let open_files_tab =
-        MenuItem::with_id(app, MENU_ITEM_OPEN_FILES_TAB_ID, "Files", true, None::<&str>)?;
+        MenuItem::with_id(app, MENU_ITEM_OPEN_FILES_TAB_ID, "Files", true, shortcut("openFilesTab"))?;
     let open_terminal_tab = MenuItem::with_id(
         app,
         MENU_ITEM_OPEN_TERMINAL_TAB_ID,
         "Terminal",
         true,
-        Some("Cmd+T"),
+        shortcut("openTerminalTab"),
     )?;
 
     let theme_light =
@@ -229,7 +366,7 @@ fn build_macos_menu<R: tauri::Runtime>(
         MENU_ITEM_TOGGLE_SIDEBAR_ID,
         "Toggle Session Sidebar",
         true,
-        Some("Cmd+L"),
+        shortcut("toggleSidebar"),
     )?;
 
     let toggle_memory_debug = MenuItem::with_id(
@@ -237,7 +374,7 @@ fn build_macos_menu<R: tauri::Runtime>(
         MENU_ITEM_TOGGLE_MEMORY_DEBUG_ID,
         "Toggle Memory Debug",
         true,
-        Some("Cmd+Shift+D"),
+        shortcut("toggleMemoryDebug"),
     )?;
 
     let help_dialog = MenuItem::with_id(
@@ -245,7 +382,7 @@ fn build_macos_menu<R: tauri::Runtime>(
         MENU_ITEM_HELP_DIALOG_ID,
         "Keyboard Shortcuts",
         true,
-        Some("Cmd+."),
+        shortcut("helpDialog"),
     )?;
 
     let download_logs = MenuItem::with_id(
@@ -253,7 +390,7 @@ fn build_macos_menu<R: tauri::Runtime>(
         MENU_ITEM_DOWNLOAD_LOGS_ID,
         "Show Diagnostics",
         true,
-        Some("Cmd+Shift+L"),
+        shortcut("downloadLogs"),
     )?;
 
     let report_bug =
@@ -388,17 +525,16 @@ fn desktop_set_auto_worktree_menu(app: tauri::AppHandle, enabled: bool) -> Resul
 
     #[cfg(target_os = "macos")]
     {
-        use tauri::menu::MenuItemKind;
-
         let new_session_shortcut = if enabled { "Cmd+Shift+N" } else { "Cmd+N" };
         let new_worktree_shortcut = if enabled { "Cmd+N" } else { "Cmd+Shift+N" };
 
         if let Some(menu) = app.menu() {
-            if let Some(MenuItemKind::MenuItem(item)) = menu.get(MENU_ITEM_NEW_SESSION_ID) {
+            let items = menu.items().map_err(|err| err.to_string())?;
+            if let Some(item) = find_menu_item(items.clone(), MENU_ITEM_NEW_SESSION_ID) {
                 item.set_accelerator(Some(new_session_shortcut))
                     .map_err(|err| err.to_string())?;
             }
-            if let Some(MenuItemKind::MenuItem(item)) = menu.get(MENU_ITEM_WORKTREE_CREATOR_ID) {
+            if let Some(item) = find_menu_item(items, MENU_ITEM_WORKTREE_CREATOR_ID) {
                 item.set_accelerator(Some(new_worktree_shortcut))
                     .map_err(|err| err.to_string())?;
             }
@@ -412,27 +548,290 @@ fn desktop_set_auto_worktree_menu(app: tauri::AppHandle, enabled: bool) -> Resul
     Ok(())
 }
 
+/// Find a menu item anywhere in the tree. `Menu::get`/`Submenu::get` only look at
+/// direct children, and every app menu item lives inside a submenu.
+#[cfg(any(target_os = "macos", test))]
+fn find_menu_item<R: tauri::Runtime>(
+    items: Vec<tauri::menu::MenuItemKind<R>>,
+    id: &str,
+) -> Option<tauri::menu::MenuItem<R>> {
+    use tauri::menu::MenuItemKind;
+
+    for item in items {
+        match item {
+            MenuItemKind::MenuItem(item) if item.id() == id => return Some(item),
+            MenuItemKind::Submenu(submenu) => {
+                if let Some(found) = find_menu_item(submenu.items().unwrap_or_default(), id) {
+                    return Some(found);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Effective accelerator per remappable menu action (`null` when unbound).
 #[tauri::command]
-fn desktop_open_path(path: String, app: Option<String>) -> Result<(), String> {
-    let trimmed = path.trim();
-    if trimmed.is_empty() {
+fn desktop_get_menu_shortcuts() -> Result<HashMap<String, Option<String>>, String> {
+    #[cfg(target_os = "macos")]
+    {
+        return Ok(resolved_menu_shortcuts()
+            .into_iter()
+            .map(|(action, accel)| (action.to_string(), accel))
+            .collect());
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        Err("desktop_get_menu_shortcuts is only supported on macOS".to_string())
+    }
+}
+
+/// Rebind (or with `None`, unbind) a menu action, updating the live menu item and
+/// persisting to `desktopMenuShortcuts`. Rejects accelerators that are reserved or
+/// already bound to another action.
+#[tauri::command]
+fn desktop_set_menu_shortcut(
+    app: tauri::AppHandle,
+    action: String,
+    accelerator: Option<String>,
+) -> Result<(), String> {
+    #[cfg(target_os = "macos")]
+    {
+        let Some((_, item_id, _)) = MENU_SHORTCUT_ACTIONS.iter().find(|(name, _, _)| *name == action) else {
+            return Err(format!("Unknown menu action: {action}"));
+        };
+        let accelerator = accelerator.map(|value| value.trim().to_string()).filter(|value| !value.is_empty());
+
+        if let Some(accel) = accelerator.as_deref() {
+            let normalized = normalize_accelerator(accel).ok_or_else(|| format!("Invalid shortcut: {accel}"))?;
+            if RESERVED_MENU_SHORTCUTS
+                .iter()
+                .any(|reserved| normalize_accelerator(reserved).as_deref() == Some(normalized.as_str()))
+            {
+                return Err(format!("{accel} is reserved"));
+            }
+            let conflict = resolved_menu_shortcuts().into_iter().find(|(other, bound)| {
+                *other != action.as_str()
+                    && bound.as_deref().and_then(normalize_accelerator).as_deref() == Some(normalized.as_str())
+            });
+            if let Some((other, _)) = conflict {
+                return Err(format!("{accel} is already bound to {other}"));
+            }
+        }
+
+        let menu = app.menu().ok_or_else(|| "Application menu is not available".to_string())?;
+        let items = menu.items().map_err(|err| err.to_string())?;
+        let item = find_menu_item(items, item_id).ok_or_else(|| format!("Menu item not found: {item_id}"))?;
+        item.set_accelerator(accelerator.as_deref()).map_err(|err| err.to_string())?;
+
+        return update_settings_file(|root| {
+            let entry = root
+                .entry("desktopMenuShortcuts")
+                .or_insert_with(|| serde_json::json!({}));
+            if !entry.is_object() {
+                *entry = serde_json::json!({});
+            }
+            entry[action.as_str()] = accelerator.map(serde_json::Value::String).unwrap_or(serde_json::Value::Null);
+        })
+        .map_err(|err| err.to_string());
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = (app, action, accelerator);
+        Err("desktop_set_menu_shortcut is only supported on macOS".to_string())
+    }
+}
+
+/// One path or several for `desktop_open_path`.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum OpenPathArg {
+    One(String),
+    Many(Vec<String>),
+}
+
+/// Open one or more paths, optionally with a specific app. On macOS several paths
+/// go to a single `open` call so editors open them together in one window; elsewhere
+/// each path is opened with the default handler and failures are reported per path.
+/// Async because an unverified app name waits on `open -a` to report a miss.
+#[tauri::command]
+async fn desktop_open_path(path: OpenPathArg, app: Option<String>) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || open_paths(path, app))
+        .await
+        .map_err(|err| err.to_string())?
+}
+
+fn open_paths(path: OpenPathArg, app: Option<String>) -> Result<(), String> {
+    let paths = match path {
+        OpenPathArg::One(path) => vec![path],
+        OpenPathArg::Many(paths) => paths,
+    };
+    if paths.is_empty() {
         return Err("Path is required".to_string());
     }
 
+    let mut targets: Vec<PathBuf> = Vec::with_capacity(paths.len());
+    for path in &paths {
+        let trimmed = path.trim();
+        if trimmed.is_empty() {
+            return Err("Path is required".to_string());
+        }
+        // `open` would parse a leading dash as one of its own flags.
+        if trimmed.starts_with('-') {
+            return Err(format!("Refusing to open path that looks like an option: {trimmed}"));
+        }
+
+        let target = Path::new(trimmed);
+        match fs::metadata(target) {
+            Ok(_) => {}
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                return Err(format!("Path does not exist: {trimmed}"));
+            }
+            Err(err) => return Err(format!("Path is not readable: {trimmed} ({err})")),
+        }
+        targets.push(target.to_path_buf());
+    }
+
     #[cfg(target_os = "macos")]
     {
         let mut command = Command::new("open");
-        if let Some(app_name) = app.as_ref().map(|value| value.trim()).filter(|value| !value.is_empty()) {
-            command.arg("-a").arg(app_name);
+        let app_name = app.as_ref().map(|value| value.trim()).filter(|value| !value.is_empty());
+        let bundle_path = app_name.and_then(resolve_app_bundle_path);
+        if let Some(app_name) = app_name {
+            match bundle_path.as_ref() {
+                Some(bundle_path) => command.arg("-a").arg(bundle_path),
+                None => command.arg("-a").arg(app_name),
+            };
+        }
+        command.args(&targets);
+
+        // Without a verified bundle, let LaunchServices resolve the name (it also finds
+        // apps outside the directories we scan) and wait so a miss surfaces as an error.
+        if let (Some(app_name), None) = (app_name, bundle_path) {
+            let output = command.output().map_err(|err| err.to_string())?;
+            if !output.status.success() {
+                let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+                return Err(if stderr.is_empty() {
+                    format!("Application is not installed: {app_name}")
+                } else {
+                    stderr
+                });
+            }
+            return Ok(());
         }
-        command.arg(trimmed);
+
         command.spawn().map_err(|err| err.to_string())?;
         return Ok(());
     }
 
     #[cfg(not(target_os = "macos"))]
     {
-        Err("desktop_open_path is only supported on macOS".to_string())
+        if app.as_deref().is_some_and(is_nonempty_string) {
+            return Err("Opening with a specific application is only supported on macOS".to_string());
+        }
+        let opener = if cfg!(target_os = "windows") { "explorer" } else { "xdg-open" };
+        let failures: Vec<String> = targets
+            .iter()
+            .filter_map(|target| {
+                Command::new(opener)
+                    .arg(target)
+                    .spawn()
+                    .err()
+                    .map(|err| format!("{}: {err}", target.display()))
+            })
+            .collect();
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(format!("Failed to open {} path(s): {}", failures.len(), failures.join("; ")))
+        }
+    }
+}
+
+/// Resolve an app name (e.g. "Visual Studio Code") to its bundle path, or `None`
+/// when it isn't installed. Always `None` off macOS.
+#[tauri::command]
+fn desktop_resolve_app_path(app_name: String) -> Result<Option<String>, String> {
+    #[cfg(target_os = "macos")]
+    {
+        return Ok(resolve_app_bundle_path(app_name.trim()).map(|path| path.to_string_lossy().into_owned()));
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = app_name;
+        Ok(None)
+    }
+}
+
+/// Reveal a path in the platform file manager with the item selected
+/// (Finder on macOS, Explorer on Windows, the parent directory via `xdg-open` on Linux).
+#[tauri::command]
+fn desktop_reveal_in_file_manager(path: String) -> Result<(), String> {
+    let trimmed = path.trim();
+    if trimmed.is_empty() {
+        return Err("Path is required".to_string());
+    }
+    let target = Path::new(trimmed);
+    if !target.exists() {
+        return Err(format!("Path does not exist: {trimmed}"));
+    }
+
+    reveal_path_in_file_manager(target)
+}
+
+/// Persistent log file (`<app log dir>/openchamber.log`), rotated by size.
+const LOG_FILE_NAME: &str = "openchamber";
+const LOG_FILE_MAX_BYTES: u128 = 5 * 1024 * 1024;
+const LOG_FILES_KEPT: usize = 5;
+
+/// Reveal the directory holding the persistent log files.
+#[tauri::command]
+fn desktop_open_log_directory(app: tauri::AppHandle) -> Result<String, String> {
+    let dir = app.path().app_log_dir().map_err(|err| err.to_string())?;
+    fs::create_dir_all(&dir).map_err(|err| err.to_string())?;
+    reveal_path_in_file_manager(&dir)?;
+    Ok(dir.to_string_lossy().to_string())
+}
+
+fn reveal_path_in_file_manager(target: &Path) -> Result<(), String> {
+    #[cfg(target_os = "macos")]
+    {
+        Command::new("open")
+            .arg("-R")
+            .arg(target)
+            .spawn()
+            .map_err(|err| err.to_string())?;
+        Ok(())
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        let mut select_arg = std::ffi::OsString::from("/select,");
+        select_arg.push(target.as_os_str());
+        Command::new("explorer")
+            .arg(select_arg)
+            .spawn()
+            .map_err(|err| err.to_string())?;
+        Ok(())
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        // xdg-open has no "select" idiom; open the containing directory instead.
+        let dir = if target.is_dir() {
+            target
+        } else {
+            target.parent().unwrap_or(target)
+        };
+        Command::new("xdg-open")
+            .arg(dir)
+            .spawn()
+            .map_err(|err| err.to_string())?;
+        Ok(())
     }
 }
 
@@ -448,10 +847,30 @@ struct InstalledAppInfo {
 struct InstalledAppsCache {
     updated_at: u64,
     apps: Vec<InstalledAppInfo>,
+    /// Pixel size the cached icons were rendered at; 0 for caches predating it.
+    #[serde(default)]
+    icon_size: u32,
+    /// The scan hit its deadline, so `apps` may be missing some installed apps.
+    #[serde(default)]
+    truncated: bool,
 }
 
 const INSTALLED_APPS_CACHE_TTL_SECS: u64 = 60 * 60 * 24;
+/// How long a truncated scan is served before the next request retries it, so a
+/// slow machine doesn't pay the full scan deadline on every request.
+#[cfg(target_os = "macos")]
+const INSTALLED_APPS_TRUNCATED_RETRY_SECS: u64 = 60 * 10;
 const INSTALLED_APPS_CACHE_FILE: &str = "discovered-apps.json";
+/// Icons are rendered at 2x the ~32pt menu size so they stay sharp on Retina.
+#[cfg(target_os = "macos")]
+const DEFAULT_APP_ICON_SIZE: u32 = 64;
+#[cfg(target_os = "macos")]
+const MIN_APP_ICON_SIZE: u32 = 16;
+#[cfg(target_os = "macos")]
+const MAX_APP_ICON_SIZE: u32 = 512;
+/// Upper bound on one installed-apps scan; past it the apps resolved so far are used.
+#[cfg(target_os = "macos")]
+const INSTALLED_APPS_SCAN_DEADLINE: Duration = Duration::from_secs(30);
 
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -459,6 +878,8 @@ struct InstalledAppsResponse {
     apps: Vec<InstalledAppInfo>,
     has_cache: bool,
     is_cache_stale: bool,
+    /// The cached list came from a scan that hit its deadline and may be partial.
+    is_truncated: bool,
 }
 
 #[tauri::command]
@@ -518,6 +939,15 @@ fn desktop_get_installed_apps(
             .as_ref()
             .map(|entry| now.saturating_sub(entry.updated_at) > INSTALLED_APPS_CACHE_TTL_SECS)
             .unwrap_or(false);
+        let is_truncated = cache.as_ref().is_some_and(|entry| entry.truncated);
+        let truncated_retry_due = cache.as_ref().is_some_and(|entry| {
+            entry.truncated && now.saturating_sub(entry.updated_at) > INSTALLED_APPS_TRUNCATED_RETRY_SECS
+        });
+        let icon_size = read_app_icon_size_from_disk();
+        let icon_size_changed = cache
+            .as_ref()
+            .map(|entry| entry.icon_size != icon_size)
+            .unwrap_or(false);
 
         if has_cache {
             if is_cache_stale {
@@ -533,60 +963,23 @@ fn desktop_get_installed_apps(
 
         if !has_cache {
             log::info!("[open-in] cache missing: refreshing app list");
-            let app_handle = app.clone();
-            let app_names = apps.clone();
-            let force_icon_refresh = false;
-            let cached_icon_map: HashMap<String, String> = HashMap::new();
-            tauri::async_runtime::spawn_blocking(move || {
-                log::info!("[open-in] scan start: {} candidates", app_names.len());
-                let refreshed = build_installed_apps(&app_names, &cached_icon_map, force_icon_refresh);
-                if log::log_enabled!(log::Level::Info) {
-                    let names: Vec<String> = refreshed.iter().map(|entry| entry.name.clone()).collect();
-                    log::info!("[open-in] scan apps: {:?}", names);
-                }
-                log::info!("[open-in] scan done: {} installed", refreshed.len());
-                let cache_entry = InstalledAppsCache {
-                    updated_at: SystemTime::now()
-                        .duration_since(UNIX_EPOCH)
-                        .map(|value| value.as_secs())
-                        .unwrap_or(0),
-                    apps: refreshed.clone(),
-                };
-                let cache_path = installed_apps_cache_path();
-                let _ = write_installed_apps_cache(&cache_path, &cache_entry);
-                dispatch_installed_apps_update(&app_handle, &refreshed);
-            });
+            spawn_installed_apps_scan(&app, apps.clone(), false, false);
         } else if force.unwrap_or(false) {
             log::info!("[open-in] manual refresh: refreshing app list");
-            let app_handle = app.clone();
-            let app_names = apps.clone();
-            let force_icon_refresh = true;
-            let cached_icon_map: HashMap<String, String> = HashMap::new();
-            tauri::async_runtime::spawn_blocking(move || {
-                log::info!("[open-in] scan start: {} candidates", app_names.len());
-                let refreshed = build_installed_apps(&app_names, &cached_icon_map, force_icon_refresh);
-                if log::log_enabled!(log::Level::Info) {
-                    let names: Vec<String> = refreshed.iter().map(|entry| entry.name.clone()).collect();
-                    log::info!("[open-in] scan apps: {:?}", names);
-                }
-                log::info!("[open-in] scan done: {} installed", refreshed.len());
-                let cache_entry = InstalledAppsCache {
-                    updated_at: SystemTime::now()
-                        .duration_since(UNIX_EPOCH)
-                        .map(|value| value.as_secs())
-                        .unwrap_or(0),
-                    apps: refreshed.clone(),
-                };
-                let cache_path = installed_apps_cache_path();
-                let _ = write_installed_apps_cache(&cache_path, &cache_entry);
-                dispatch_installed_apps_update(&app_handle, &refreshed);
-            });
+            spawn_installed_apps_scan(&app, apps.clone(), true, true);
+        } else if icon_size_changed {
+            log::info!("[open-in] icon size changed to {icon_size}px: re-rendering icons");
+            spawn_installed_apps_scan(&app, apps.clone(), true, false);
+        } else if truncated_retry_due {
+            log::info!("[open-in] cached scan was truncated: retrying");
+            spawn_installed_apps_scan(&app, apps.clone(), false, false);
         }
 
         return Ok(InstalledAppsResponse {
             apps: cached_apps,
             has_cache,
             is_cache_stale,
+            is_truncated,
         });
     }
 
@@ -597,50 +990,207 @@ fn desktop_get_installed_apps(
     }
 }
 
+/// Installed-apps scan bookkeeping. `in_flight` is set while a scan runs so concurrent
+/// callers (e.g. several windows on a cold cache) don't each start their own
+/// `mdfind`/`sips` storm; `pending` holds a manual refresh (app list and its icon
+/// refresh flag) that arrived mid-scan, so the running scan does one more pass for it.
+#[cfg(target_os = "macos")]
+struct InstalledAppsScanState {
+    in_flight: bool,
+    pending: Option<(Vec<String>, bool)>,
+}
+
+#[cfg(target_os = "macos")]
+static INSTALLED_APPS_SCAN: Mutex<InstalledAppsScanState> = Mutex::new(InstalledAppsScanState {
+    in_flight: false,
+    pending: None,
+});
+
+/// Start a background installed-apps scan unless one is already running. Callers that
+/// are coalesced receive the running scan's `installed-apps-updated` event; only an
+/// explicit user refresh (`queue_if_busy`) is queued for one more pass afterwards.
+#[cfg(target_os = "macos")]
+fn spawn_installed_apps_scan(
+    app: &tauri::AppHandle,
+    app_names: Vec<String>,
+    force_icon_refresh: bool,
+    queue_if_busy: bool,
+) {
+    {
+        let mut state = INSTALLED_APPS_SCAN.lock().expect("installed apps scan mutex");
+        if state.in_flight {
+            if queue_if_busy {
+                log::info!("[open-in] scan already in flight: queueing manual refresh");
+                state.pending = Some((app_names, force_icon_refresh));
+            } else {
+                log::info!("[open-in] scan already in flight: coalescing request");
+            }
+            return;
+        }
+        state.in_flight = true;
+    }
+
+    let app_handle = app.clone();
+    let cached_icon_map: HashMap<String, String> = HashMap::new();
+    let icon_size = read_app_icon_size_from_disk();
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut app_names = app_names;
+        let mut force_icon_refresh = force_icon_refresh;
+        loop {
+            log::info!("[open-in] scan start: {} candidates", app_names.len());
+            let deadline = std::time::Instant::now() + INSTALLED_APPS_SCAN_DEADLINE;
+            let scan = build_installed_apps(&app_names, &cached_icon_map, force_icon_refresh, icon_size, deadline);
+            let refreshed = scan.apps;
+
+            if APP_EXITING.load(Ordering::SeqCst) {
+                log::info!("[open-in] scan cancelled: app is quitting");
+                let mut state = INSTALLED_APPS_SCAN.lock().expect("installed apps scan mutex");
+                state.in_flight = false;
+                state.pending = None;
+                return;
+            }
+
+            if log::log_enabled!(log::Level::Info) {
+                let names: Vec<String> = refreshed.iter().map(|entry| entry.name.clone()).collect();
+                log::info!("[open-in] scan apps: {:?}", names);
+            }
+            log::info!("[open-in] scan done: {} installed (truncated={})", refreshed.len(), scan.truncated);
+
+            // A truncated scan is cached marked as such and retried after a back-off.
+            let cache_entry = InstalledAppsCache {
+                updated_at: SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|value| value.as_secs())
+                    .unwrap_or(0),
+                apps: refreshed.clone(),
+                icon_size,
+                truncated: scan.truncated,
+            };
+            let cache_path = installed_apps_cache_path();
+            let _ = write_installed_apps_cache(&cache_path, &cache_entry);
+            dispatch_installed_apps_update(&app_handle, &refreshed, scan.truncated);
+
+            let pending = {
+                let mut state = INSTALLED_APPS_SCAN.lock().expect("installed apps scan mutex");
+                let pending = state.pending.take();
+                if pending.is_none() {
+                    state.in_flight = false;
+                }
+                pending
+            };
+            let Some((next_names, next_force)) = pending else {
+                break;
+            };
+            log::info!("[open-in] running queued rescan");
+            app_names = next_names;
+            force_icon_refresh = next_force;
+        }
+    });
+}
+
 #[derive(Serialize)]
 struct AppIconPayload {
     app: String,
     data_url: String,
 }
 
+/// Icons for `apps` at `size` pixels (default `desktopAppIconSize`, else 64). Icons
+/// already in a fresh installed-apps cache at that size are reused.
 #[tauri::command]
-fn desktop_fetch_app_icons(apps: Vec<String>) -> Result<Vec<AppIconPayload>, String> {
+fn desktop_fetch_app_icons(apps: Vec<String>, size: Option<u32>) -> Result<Vec<AppIconPayload>, String> {
     #[cfg(target_os = "macos")]
     {
-        let mut results: Vec<AppIconPayload> = Vec::new();
+        return Ok(fetch_app_icons(&apps, resolve_app_icon_size(size)));
+    }
 
-        for raw in apps {
-            let trimmed = raw.trim();
-            if trimmed.is_empty() {
-                continue;
-            }
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = (apps, size);
+        Err("desktop_fetch_app_icons is only supported on macOS".to_string())
+    }
+}
 
-            let Some(app_path) = resolve_app_bundle_path(trimmed) else {
-                continue;
-            };
+/// Single-app variant of `desktop_fetch_app_icons` for lazily loaded menus; renders
+/// only on a cache miss. `None` when the app or its icon can't be found.
+#[tauri::command]
+fn desktop_fetch_app_icon(app: String, size: Option<u32>) -> Result<Option<String>, String> {
+    #[cfg(target_os = "macos")]
+    {
+        let icons = fetch_app_icons(std::slice::from_ref(&app), resolve_app_icon_size(size));
+        return Ok(icons.into_iter().next().map(|icon| icon.data_url));
+    }
 
-            let Some(icon_path) = resolve_app_icon_path(&app_path) else {
-                continue;
-            };
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = (app, size);
+        Err("desktop_fetch_app_icon is only supported on macOS".to_string())
+    }
+}
 
-            let Some(data_url) = icon_to_data_url(&icon_path, trimmed) else {
-                continue;
-            };
+#[cfg(target_os = "macos")]
+fn resolve_app_icon_size(size: Option<u32>) -> u32 {
+    size.map(|value| value.clamp(MIN_APP_ICON_SIZE, MAX_APP_ICON_SIZE))
+        .unwrap_or_else(read_app_icon_size_from_disk)
+}
 
-            results.push(AppIconPayload {
-                app: trimmed.to_string(),
-                data_url,
-            });
+#[cfg(target_os = "macos")]
+fn fetch_app_icons(apps: &[String], icon_size: u32) -> Vec<AppIconPayload> {
+    let cache_path = installed_apps_cache_path();
+    let mut cache = read_installed_apps_cache(&cache_path);
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|value| value.as_secs())
+        .unwrap_or(0);
+    let cache_usable = cache.as_ref().is_some_and(|entry| {
+        entry.icon_size == icon_size && now.saturating_sub(entry.updated_at) <= INSTALLED_APPS_CACHE_TTL_SECS
+    });
+
+    let mut results: Vec<AppIconPayload> = Vec::new();
+    let mut rendered_any = false;
+    for raw in apps {
+        let trimmed = raw.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        let cached_entry = cache
+            .as_mut()
+            .filter(|_| cache_usable)
+            .and_then(|entry| entry.apps.iter_mut().find(|app| app.name == trimmed));
+        if let Some(data_url) = cached_entry.as_ref().and_then(|app| app.icon_data_url.clone()) {
+            results.push(AppIconPayload { app: trimmed.to_string(), data_url });
+            continue;
         }
 
-        return Ok(results);
+        let Some(data_url) = render_app_icon(trimmed, icon_size) else {
+            continue;
+        };
+        if let Some(app) = cached_entry {
+            app.icon_data_url = Some(data_url.clone());
+            rendered_any = true;
+        }
+        results.push(AppIconPayload { app: trimmed.to_string(), data_url });
     }
 
-    #[cfg(not(target_os = "macos"))]
-    {
-        let _ = apps;
-        Err("desktop_fetch_app_icons is only supported on macOS".to_string())
+    if rendered_any {
+        if let Some(cache) = cache.as_ref() {
+            let _ = write_installed_apps_cache(&cache_path, cache);
+        }
     }
+
+    results
+}
+
+#[cfg(target_os = "macos")]
+fn render_app_icon(app_name: &str, icon_size: u32) -> Option<String> {
+    let app_path = resolve_app_bundle_path(app_name)?;
+    render_bundle_icon(&app_path, app_name, icon_size)
+}
+
+#[cfg(target_os = "macos")]
+fn render_bundle_icon(app_path: &Path, app_name: &str, icon_size: u32) -> Option<String> {
+    let icon_path = resolve_app_icon_path(app_path)?;
+    icon_to_data_url(&icon_path, app_name, icon_size)
 }
 
 #[cfg(target_os = "macos")]
@@ -662,16 +1212,15 @@ fn resolve_app_bundle_path(app_name: &str) -> Option<PathBuf> {
     ];
 
     for candidate in candidates {
-        let path = PathBuf::from(&candidate);
-        if path.exists() {
+        if let Some(path) = verified_app_bundle(Path::new(&candidate)) {
             return Some(path);
         }
     }
 
     if let Some(home) = env::var_os("HOME") {
         let user_app_path = PathBuf::from(home).join("Applications").join(&bundle_name);
-        if user_app_path.exists() {
-            return Some(user_app_path);
+        if let Some(path) = verified_app_bundle(&user_app_path) {
+            return Some(path);
         }
     }
 
@@ -683,8 +1232,7 @@ fn resolve_app_bundle_path(app_name: &str) -> Option<PathBuf> {
                 if trimmed.is_empty() {
                     continue;
                 }
-                let path = PathBuf::from(trimmed);
-                if path.exists() {
+                if let Some(path) = verified_app_bundle(Path::new(trimmed)) {
                     return Some(path);
                 }
             }
@@ -694,13 +1242,26 @@ fn resolve_app_bundle_path(app_name: &str) -> Option<PathBuf> {
     None
 }
 
+/// Pixel size app icons are rendered at (`desktopAppIconSize`, clamped to 16..=512).
+#[cfg(target_os = "macos")]
+fn read_app_icon_size_from_disk() -> u32 {
+    read_settings_value("desktopAppIconSize")
+        .and_then(|v| v.as_u64())
+        .map(|v| v.clamp(MIN_APP_ICON_SIZE as u64, MAX_APP_ICON_SIZE as u64) as u32)
+        .unwrap_or(DEFAULT_APP_ICON_SIZE)
+}
+
+/// Follow symlinks to the real bundle and check it is one (has `Contents/`), so a
+/// dangling link or a stray file named `Foo.app` isn't reported as installed.
+#[cfg(target_os = "macos")]
+fn verified_app_bundle(path: &Path) -> Option<PathBuf> {
+    let real = fs::canonicalize(path).ok()?;
+    real.join("Contents").is_dir().then_some(real)
+}
+
 #[cfg(target_os = "macos")]
 fn installed_apps_cache_path() -> PathBuf {
-    let home = env::var_os("HOME").map(PathBuf::from).unwrap_or_else(|| PathBuf::from("/"));
-    home
-        .join(".config")
-        .join("openchamber")
-        .join(INSTALLED_APPS_CACHE_FILE)
+    openchamber_data_dir().join(INSTALLED_APPS_CACHE_FILE)
 }
 
 #[cfg(target_os = "macos")]
@@ -718,29 +1279,44 @@ fn write_installed_apps_cache(path: &Path, cache: &InstalledAppsCache) -> Result
     fs::write(path, payload).map_err(|err| err.to_string())
 }
 
+#[cfg(target_os = "macos")]
+struct InstalledAppsScan {
+    apps: Vec<InstalledAppInfo>,
+    /// Stopped early at the deadline or because the app is quitting.
+    truncated: bool,
+}
+
+/// Resolve each app (and its icon), checking between apps for shutdown and `deadline`
+/// so a slow scan neither outlives the app nor blocks the open-in menu indefinitely.
 #[cfg(target_os = "macos")]
 fn build_installed_apps(
     apps: &[String],
     cached_icon_map: &HashMap<String, String>,
     force_icon_refresh: bool,
-) -> Vec<InstalledAppInfo> {
+    icon_size: u32,
+    deadline: std::time::Instant,
+) -> InstalledAppsScan {
     let mut seen = HashSet::new();
     let mut results = Vec::new();
 
     for raw in apps {
-        let trimmed = raw.trim();
+        if APP_EXITING.load(Ordering::SeqCst) || std::time::Instant::now() >= deadline {
+            return InstalledAppsScan { apps: results, truncated: true };
+        }
+
+        let trimmed = raw.trim();
         if trimmed.is_empty() || !seen.insert(trimmed.to_string()) {
             continue;
         }
 
         if let Some(app_path) = resolve_app_bundle_path(trimmed) {
             let icon_data_url = if force_icon_refresh {
-                resolve_app_icon_path(&app_path).and_then(|icon| icon_to_data_url(&icon, trimmed))
+                render_bundle_icon(&app_path, trimmed, icon_size)
             } else {
                 cached_icon_map
                     .get(trimmed)
                     .cloned()
-                    .or_else(|| resolve_app_icon_path(&app_path).and_then(|icon| icon_to_data_url(&icon, trimmed)))
+                    .or_else(|| render_bundle_icon(&app_path, trimmed, icon_size))
             };
             results.push(InstalledAppInfo {
                 name: trimmed.to_string(),
@@ -749,14 +1325,15 @@ fn build_installed_apps(
         }
     }
 
-    results
+    InstalledAppsScan { apps: results, truncated: false }
 }
 
 #[cfg(target_os = "macos")]
-fn dispatch_installed_apps_update(app: &tauri::AppHandle, apps: &[InstalledAppInfo]) {
+fn dispatch_installed_apps_update(app: &tauri::AppHandle, apps: &[InstalledAppInfo], truncated: bool) {
     let event = serde_json::to_string("openchamber:installed-apps-updated")
         .unwrap_or_else(|_| "\"openchamber:installed-apps-updated\"".into());
-    let detail = serde_json::to_string(apps).unwrap_or_else(|_| "[]".into());
+    let detail = serde_json::to_string(&serde_json::json!({ "apps": apps, "truncated": truncated }))
+        .unwrap_or_else(|_| "{}".into());
     let script = format!("window.dispatchEvent(new CustomEvent({event}, {{ detail: {detail} }}));");
     eval_in_all_windows(app, &script);
 }
@@ -847,12 +1424,27 @@ fn read_bundle_icon_file(app_path: &Path) -> Option<String> {
     Some(icon_file)
 }
 
+/// Set once a missing `sips` has been reported, so the warning isn't repeated per icon.
 #[cfg(target_os = "macos")]
-fn icon_to_data_url(icon_path: &Path, app_name: &str) -> Option<String> {
+static SIPS_MISSING_LOGGED: AtomicBool = AtomicBool::new(false);
+
+#[cfg(target_os = "macos")]
+fn icon_to_data_url(icon_path: &Path, app_name: &str, size: u32) -> Option<String> {
     if !icon_path.exists() {
         return None;
     }
 
+    let bytes = match render_icon_with_sips(icon_path, app_name, size) {
+        Some(bytes) => bytes,
+        None => icns_png_data(icon_path, size)?,
+    };
+
+    let encoded = general_purpose::STANDARD.encode(bytes);
+    Some(format!("data:image/png;base64,{encoded}"))
+}
+
+#[cfg(target_os = "macos")]
+fn render_icon_with_sips(icon_path: &Path, app_name: &str, size: u32) -> Option<Vec<u8>> {
     let sanitized: String = app_name
         .chars()
         .map(|ch| if ch.is_ascii_alphanumeric() { ch } else { '_' })
@@ -863,19 +1455,27 @@ fn icon_to_data_url(icon_path: &Path, app_name: &str) -> Option<String> {
         .unwrap_or(0);
     let tmp_path = env::temp_dir().join(format!("openchamber-icon-{sanitized}-{timestamp}.png"));
 
-    let status = Command::new("sips")
+    let status = match Command::new("sips")
         .args([
             "-s",
             "format",
             "png",
             "-Z",
-            "32",
+            &size.to_string(),
             &icon_path.to_string_lossy(),
             "--out",
             &tmp_path.to_string_lossy(),
         ])
         .status()
-        .ok()?;
+    {
+        Ok(status) => status,
+        Err(err) => {
+            if !SIPS_MISSING_LOGGED.swap(true, Ordering::Relaxed) {
+                log::warn!("[open-in] sips unavailable ({err}); falling back to embedded icns PNGs");
+            }
+            return None;
+        }
+    };
 
     if !status.success() {
         return None;
@@ -886,9 +1486,47 @@ fn icon_to_data_url(icon_path: &Path, app_name: &str) -> Option<String> {
     if bytes.is_empty() {
         return None;
     }
+    Some(bytes)
+}
 
-    let encoded = general_purpose::STANDARD.encode(bytes);
-    Some(format!("data:image/png;base64,{encoded}"))
+/// Pull an embedded PNG out of an `.icns` container without external tools, picking
+/// the smallest one at least `size` wide (else the largest). Modern icns files store
+/// their larger sizes as plain PNG; bitmap-only legacy icons yield `None`.
+#[cfg(target_os = "macos")]
+fn icns_png_data(icon_path: &Path, size: u32) -> Option<Vec<u8>> {
+    const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+    let bytes = fs::read(icon_path).ok()?;
+    if bytes.len() < 8 || &bytes[0..4] != b"icns" {
+        return None;
+    }
+
+    let mut best: Option<(u32, &[u8])> = None;
+    let mut offset = 8;
+    while offset + 8 <= bytes.len() {
+        let len = u32::from_be_bytes(bytes[offset + 4..offset + 8].try_into().ok()?) as usize;
+        if len < 8 || offset + len > bytes.len() {
+            break;
+        }
+        let data = &bytes[offset + 8..offset + len];
+        offset += len;
+
+        // The IHDR chunk follows the signature; its width is at byte 16.
+        if !data.starts_with(PNG_SIGNATURE) || data.len() < 24 {
+            continue;
+        }
+        let width = u32::from_be_bytes(data[16..20].try_into().ok()?);
+        let is_better = match best {
+            None => true,
+            Some((best_width, _)) if best_width >= size => width >= size && width < best_width,
+            Some((best_width, _)) => width > best_width,
+        };
+        if is_better {
+            best = Some((width, data));
+        }
+    }
+
+    best.map(|(_, data)| data.to_vec())
 }
 
 #[cfg(target_os = "macos")]
@@ -928,6 +1566,13 @@ const SIDECAR_NAME: &str = "openchamber-server";
 const SIDECAR_NOTIFY_PREFIX: &str = "[OpenChamberDesktopNotify] ";
 const HEALTH_TIMEOUT: Duration = Duration::from_secs(20);
 const HEALTH_POLL_INTERVAL: Duration = Duration::from_millis(250);
+const HEALTH_POLL_MAX_INTERVAL: Duration = Duration::from_secs(2);
+/// Consecutive healthy responses needed before a server is accepted, so one that
+/// answers mid-initialization isn't taken as ready.
+const HEALTH_REQUIRED_SUCCESSES: u32 = 2;
+const SIDECAR_OUTPUT_HISTORY: usize = 200;
+/// Log target for sidecar lifecycle events so diagnostics can filter on it.
+const SIDECAR_LOG_TARGET: &str = "sidecar";
 
 const DEFAULT_DESKTOP_PORT: u16 = 57123;
 const WINDOW_STATE_DEBOUNCE_MS: u64 = 300;
@@ -935,13 +1580,63 @@ const MIN_WINDOW_WIDTH: u32 = 800;
 const MIN_WINDOW_HEIGHT: u32 = 520;
 const MIN_RESTORE_WINDOW_WIDTH: u32 = 900;
 const MIN_RESTORE_WINDOW_HEIGHT: u32 = 560;
+const DEFAULT_WINDOW_WIDTH: f64 = 1280.0;
+const DEFAULT_WINDOW_HEIGHT: f64 = 800.0;
+const WINDOW_CASCADE_OFFSET: f64 = 30.0;
+/// Reopen/New Window triggers closer together than this open only one window.
+const NEW_WINDOW_DEBOUNCE: Duration = Duration::from_millis(750);
+/// Hidden windows are shown after this even if the page never reports ready.
+const WINDOW_READY_TIMEOUT: Duration = Duration::from_secs(5);
+const DEFAULT_TRAFFIC_LIGHT_INSET: TrafficLightInset = TrafficLightInset { x: 17.0, y: 26.0 };
+const MAX_TRAFFIC_LIGHT_INSET: f64 = 200.0;
 
 const LOCAL_HOST_ID: &str = "local";
+const HOST_INIT_SCRIPT_MAX_BYTES: usize = 16 * 1024;
+const HOST_MONITOR_INTERVAL: Duration = Duration::from_secs(30);
 
 #[derive(Default)]
 struct SidecarState {
     child: Mutex<Option<CommandChild>>,
     url: Mutex<Option<String>>,
+    /// PATH handed to the sidecar, kept for diagnostics.
+    path: Mutex<Option<String>>,
+    /// HOME handed to the sidecar (resolved by Tauri, not taken from the env).
+    home: Mutex<Option<String>>,
+    /// Most recent sidecar stdout/stderr lines, oldest first.
+    recent_output: Mutex<VecDeque<String>>,
+    /// When the current sidecar process was spawned.
+    started_at: Mutex<Option<SystemTime>>,
+    last_health: Mutex<Option<SidecarHealthCheck>>,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SidecarHealthCheck {
+    healthy: bool,
+    /// Unix time in milliseconds.
+    checked_at: u64,
+}
+
+impl SidecarState {
+    fn record_health(&self, healthy: bool) {
+        let checked_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|value| value.as_millis() as u64)
+            .unwrap_or(0);
+        *self.last_health.lock().expect("sidecar health mutex") = Some(SidecarHealthCheck { healthy, checked_at });
+    }
+
+    fn record_output(&self, line: &str) {
+        let line = line.trim_end();
+        if line.is_empty() {
+            return;
+        }
+        let mut guard = self.recent_output.lock().expect("sidecar output mutex");
+        if guard.len() >= SIDECAR_OUTPUT_HISTORY {
+            guard.pop_front();
+        }
+        guard.push_back(line.to_string());
+    }
 }
 
 /// Holds the initialization script and local origin, shared across all windows.
@@ -952,6 +1647,17 @@ struct DesktopUiInjectionState {
     /// Host URLs that were probed unreachable (e.g. at startup).
     /// `open_new_window` checks this to avoid opening windows at dead hosts.
     unreachable_hosts: Mutex<HashSet<String>>,
+    /// Host URLs that answered the startup probe with 401/403. Windows loading
+    /// these hosts get an `openchamber:host-auth-required` event.
+    auth_required_hosts: Mutex<HashSet<String>>,
+    /// Host id (`local` or a configured host) each window label is pinned to.
+    /// Only changes when the window lands on another known host, so login
+    /// redirects and external links don't lose track of it.
+    window_hosts: Mutex<HashMap<String, String>>,
+    /// Active project path each window reported via `desktop_set_window_project`.
+    window_projects: Mutex<HashMap<String, String>>,
+    /// Windows built hidden that are waiting for `desktop_window_ready`.
+    pending_reveal: Mutex<HashSet<String>>,
 }
 
 /// Tracks the set of currently-focused window labels.
@@ -1000,6 +1706,12 @@ struct DesktopHost {
     id: String,
     label: String,
     url: String,
+    /// JS appended to the init script of windows opened at this host.
+    ///
+    /// Security-sensitive: it runs with full access to the page and the Tauri IPC
+    /// bridge, so only set it for instances you control.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    init_script: Option<String>,
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -1022,6 +1734,22 @@ struct DesktopWindowState {
     fullscreen: bool,
 }
 
+/// One entry of the persisted window session (`desktopSessionWindows`).
+/// Local windows store `local: true` so they can be rebased onto the current
+/// sidecar port on the next launch.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DesktopSessionWindow {
+    url: String,
+    #[serde(default)]
+    local: bool,
+    #[serde(default)]
+    state: Option<DesktopWindowState>,
+    /// Workspace identity at capture time; see `window_identity`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    identity: Option<String>,
+}
+
 #[derive(Default)]
 struct WindowGeometryDebounceState {
     revisions: Mutex<HashMap<String, u64>>,
@@ -1046,67 +1774,272 @@ fn normalize_host_url(raw: &str) -> Option<String> {
     Some(normalized)
 }
 
-fn settings_file_path() -> PathBuf {
+/// Trim a per-host init script, treating blank as unset. Errors when it exceeds
+/// `HOST_INIT_SCRIPT_MAX_BYTES`.
+fn normalize_host_init_script(raw: Option<&str>) -> Result<Option<String>> {
+    let Some(script) = raw.map(str::trim).filter(|s| !s.is_empty()) else {
+        return Ok(None);
+    };
+    if script.len() > HOST_INIT_SCRIPT_MAX_BYTES {
+        return Err(anyhow!(
+            "Host init script is {} bytes; the limit is {HOST_INIT_SCRIPT_MAX_BYTES}",
+            script.len()
+        ));
+    }
+    Ok(Some(script.to_string()))
+}
+
+/// Init script configured for the host serving `url`, wrapped so it only runs on
+/// that host's origin (the window may later navigate elsewhere) and can't throw.
+fn host_init_script_for_url(url: &url::Url) -> Option<String> {
+    let host_url = normalize_host_url(url.as_str())?;
+    let snippet = read_desktop_hosts_config_from_disk()
+        .hosts
+        .into_iter()
+        .find(|host| host.url == host_url)
+        .and_then(|host| host.init_script)?;
+    let origin_json = serde_json::to_string(&host_url).ok()?;
+    Some(format!(
+        "\n(function(){{try{{if(window.location.origin!=={origin_json})return;\n{snippet}\n}}catch(_e){{}}}})();"
+    ))
+}
+
+/// Trim a host label and collapse internal runs of whitespace to single spaces.
+fn normalize_host_label(raw: &str) -> String {
+    raw.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Return `label`, or `label (N)` with the first free N when it's already taken
+/// (compared case-insensitively). Records the chosen label in `used`.
+fn dedupe_host_label(label: String, used: &mut HashSet<String>) -> String {
+    if used.insert(label.to_lowercase()) {
+        return label;
+    }
+    let mut n = 2;
+    loop {
+        let candidate = format!("{label} ({n})");
+        if used.insert(candidate.to_lowercase()) {
+            return candidate;
+        }
+        n += 1;
+    }
+}
+
+/// Directory for OpenChamber's own state (settings, caches). Honors
+/// `OPENCHAMBER_DATA_DIR`, falling back to `~/.config/openchamber`.
+fn openchamber_data_dir() -> PathBuf {
     if let Ok(dir) = env::var("OPENCHAMBER_DATA_DIR") {
         if !dir.trim().is_empty() {
-            return PathBuf::from(dir.trim()).join("settings.json");
+            return PathBuf::from(dir.trim());
         }
     }
     let home = env::var("HOME").unwrap_or_default();
-    PathBuf::from(home)
-        .join(".config")
-        .join("openchamber")
-        .join("settings.json")
+    PathBuf::from(home).join(".config").join("openchamber")
 }
 
-fn read_desktop_local_port_from_disk() -> Option<u16> {
-    let path = settings_file_path();
-    let raw = fs::read_to_string(path).ok();
-    let parsed = raw
-        .as_deref()
-        .and_then(|s| serde_json::from_str::<serde_json::Value>(s).ok());
-    parsed
-        .as_ref()
-        .and_then(|v| v.get("desktopLocalPort"))
-        .and_then(|v| v.as_u64())
-        .and_then(|v| if v > 0 && v <= u16::MAX as u64 { Some(v as u16) } else { None })
+fn settings_file_path() -> PathBuf {
+    openchamber_data_dir().join("settings.json")
 }
 
-fn write_desktop_local_port_to_disk(port: u16) -> Result<()> {
+/// Parsed settings.json, or `None` if it is missing or unparsable. Readers that need
+/// several keys take them from one snapshot so they stay consistent with each other.
+fn read_settings_snapshot() -> Option<serde_json::Value> {
+    let raw = fs::read_to_string(settings_file_path()).ok()?;
+    serde_json::from_str::<serde_json::Value>(&raw).ok()
+}
+
+/// `key` from settings.json, or `None` if the file is missing, unparsable or lacks it.
+fn read_settings_value(key: &str) -> Option<serde_json::Value> {
+    read_settings_snapshot()?.get_mut(key).map(serde_json::Value::take)
+}
+
+/// Boolean `key` from settings.json, `default` when absent or not a bool.
+fn read_settings_bool(key: &str, default: bool) -> bool {
+    read_settings_value(key).and_then(|v| v.as_bool()).unwrap_or(default)
+}
+
+/// Serializes settings.json read-modify-write cycles across windows and commands.
+static SETTINGS_WRITE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Where a settings.json that failed to parse was moved this session, if anywhere.
+static SETTINGS_CORRUPT_BACKUP: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// App handle used to broadcast `openchamber:settings-changed`; set once in setup.
+static SETTINGS_EVENT_APP: std::sync::OnceLock<tauri::AppHandle> = std::sync::OnceLock::new();
+
+/// Load settings.json as an object for a read-modify-write.
+///
+/// A file that exists but isn't a JSON object is moved aside to
+/// `settings.json.corrupt-<ms>` first, so the write that follows can't replace the
+/// user's configuration with an empty base. Errors if that backup fails.
+fn load_settings_for_update(path: &Path) -> Result<serde_json::Map<String, serde_json::Value>> {
+    let raw = match fs::read_to_string(path) {
+        Ok(raw) => raw,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(serde_json::Map::new()),
+        Err(err) => return Err(err.into()),
+    };
+    if raw.trim().is_empty() {
+        return Ok(serde_json::Map::new());
+    }
+    if let Ok(serde_json::Value::Object(root)) = serde_json::from_str::<serde_json::Value>(&raw) {
+        return Ok(root);
+    }
+
+    backup_corrupt_settings(path)?;
+    Ok(serde_json::Map::new())
+}
+
+/// `settings.json.<tag>-<ms>` next to `path`.
+fn settings_backup_path(path: &Path, tag: &str) -> PathBuf {
+    let stamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|value| value.as_millis())
+        .unwrap_or(0);
+    let mut name = path
+        .file_name()
+        .map(|name| name.to_os_string())
+        .unwrap_or_else(|| "settings.json".into());
+    name.push(format!(".{tag}-{stamp}"));
+    path.with_file_name(name)
+}
+
+fn backup_corrupt_settings(path: &Path) -> Result<PathBuf> {
+    let backup = settings_backup_path(path, "corrupt");
+
+    fs::rename(path, &backup)
+        .map_err(|err| anyhow!("settings.json is corrupt and could not be backed up: {err}"))?;
+    log::error!("[desktop] settings.json is corrupt; moved it to {}", backup.display());
+
+    if let Ok(mut guard) = SETTINGS_CORRUPT_BACKUP.lock() {
+        *guard = Some(backup.clone());
+    }
+    Ok(backup)
+}
+
+/// Check settings.json at startup so a corrupt file is backed up (and reported to
+/// the UI) before anything writes to it.
+fn check_settings_file() {
+    let _guard = SETTINGS_WRITE_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Err(err) = load_settings_for_update(&settings_file_path()) {
+        log::error!("[desktop] {err}");
+    }
+}
+
+/// Apply `update` to the settings.json root object and write it back.
+///
+/// The whole cycle holds `SETTINGS_WRITE_LOCK`, and the file is replaced via rename
+/// so concurrent readers never see a half-written file.
+fn update_settings_file(
+    update: impl FnOnce(&mut serde_json::Map<String, serde_json::Value>),
+) -> Result<()> {
+    try_update_settings_file(|_, root| {
+        update(root);
+        Ok(())
+    })
+}
+
+/// Like `update_settings_file`, but `update` also gets the file path and can abort
+/// the write by returning an error.
+fn try_update_settings_file<T>(
+    update: impl FnOnce(&Path, &mut serde_json::Map<String, serde_json::Value>) -> Result<T>,
+) -> Result<T> {
+    let guard = SETTINGS_WRITE_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+
     let path = settings_file_path();
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent)?;
     }
 
-    let mut root: serde_json::Value = if let Ok(raw) = fs::read_to_string(&path) {
-        serde_json::from_str(&raw).unwrap_or(serde_json::json!({}))
-    } else {
-        serde_json::json!({})
+    let mut root = load_settings_for_update(&path)?;
+    let before = root.clone();
+    let result = update(&path, &mut root)?;
+
+    let mut changed: Vec<String> = root
+        .iter()
+        .filter(|(key, value)| before.get(key.as_str()) != Some(*value))
+        .map(|(key, _)| key.clone())
+        .chain(before.keys().filter(|key| !root.contains_key(key.as_str())).cloned())
+        .collect();
+    changed.sort();
+
+    let tmp_path = path.with_extension("json.tmp");
+    fs::write(&tmp_path, serde_json::to_string_pretty(&serde_json::Value::Object(root))?)?;
+    fs::rename(&tmp_path, &path)?;
+    drop(guard);
+
+    if !changed.is_empty() {
+        if let Some(app) = SETTINGS_EVENT_APP.get() {
+            let _ = app.emit("openchamber:settings-changed", serde_json::json!({ "keys": changed }));
+        }
+    }
+    Ok(result)
+}
+
+/// Tell a freshly loaded page that settings.json was found corrupt this session.
+fn dispatch_settings_corrupt<R: tauri::Runtime>(window: &tauri::Webview<R>) {
+    let backup = match SETTINGS_CORRUPT_BACKUP.lock() {
+        Ok(guard) => guard.clone(),
+        Err(_) => return,
+    };
+    let Some(backup) = backup else {
+        return;
     };
 
-    if !root.is_object() {
-        root = serde_json::json!({});
+    let payload = serde_json::json!({ "backupPath": backup.to_string_lossy() });
+    let _ = window.emit_to(window.label(), "openchamber:settings-corrupt", payload.clone());
+
+    let event = serde_json::to_string("openchamber:settings-corrupt")
+        .unwrap_or_else(|_| "\"openchamber:settings-corrupt\"".into());
+    let detail = serde_json::to_string(&payload).unwrap_or_else(|_| "{}".into());
+    let script = format!("window.dispatchEvent(new CustomEvent({event}, {{ detail: {detail} }}));");
+    let _ = window.eval(&script);
+}
+
+fn read_desktop_local_port_from_disk() -> Option<u16> {
+    read_settings_value("desktopLocalPort")
+        .and_then(|v| v.as_u64())
+        .and_then(|v| if v > 0 && v <= u16::MAX as u64 { Some(v as u16) } else { None })
+}
+
+/// Extra sidecar arguments from `sidecarArgs`, appended after the built-in `--port`.
+/// The whole list is ignored if any entry would override the port.
+fn read_sidecar_args_from_disk() -> Vec<String> {
+    let args: Vec<String> = read_settings_value("sidecarArgs")
+        .as_ref()
+        .and_then(|v| v.as_array())
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| item.as_str())
+                .map(|item| item.trim().to_string())
+                .filter(|item| !item.is_empty())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    if args.iter().any(|arg| arg == "--port" || arg.starts_with("--port=")) {
+        log::warn!(target: SIDECAR_LOG_TARGET, "event=config sidecarArgs ignored: must not override --port");
+        return Vec::new();
     }
 
-    root["desktopLocalPort"] = serde_json::Value::Number(serde_json::Number::from(port));
-    fs::write(&path, serde_json::to_string_pretty(&root)?)?;
-    Ok(())
+    args
 }
 
+fn write_desktop_local_port_to_disk(port: u16) -> Result<()> {
+    update_settings_file(|root| {
+        root.insert("desktopLocalPort".into(), serde_json::Value::Number(serde_json::Number::from(port)));
+    })
+}
 
-fn read_desktop_hosts_config_from_disk() -> DesktopHostsConfig {
-    let path = settings_file_path();
-    let raw = fs::read_to_string(path).ok();
-    let parsed = raw
-        .as_deref()
-        .and_then(|s| serde_json::from_str::<serde_json::Value>(s).ok());
 
-    let hosts_value = parsed
-        .as_ref()
-        .and_then(|v| v.get("desktopHosts"))
-        .cloned()
+fn read_desktop_hosts_config_from_disk() -> DesktopHostsConfig {
+    let mut settings = read_settings_snapshot();
+    let hosts_value = settings
+        .as_mut()
+        .and_then(|v| v.get_mut("desktopHosts"))
+        .map(serde_json::Value::take)
         .unwrap_or(serde_json::Value::Null);
-    let default_value = parsed
+    let default_value = settings
         .as_ref()
         .and_then(|v| v.get("desktopDefaultHostId"))
         .and_then(|v| v.as_str())
@@ -1120,6 +2053,13 @@ fn read_desktop_hosts_config_from_disk() -> DesktopHostsConfig {
                     continue;
                 }
                 if let Some(url) = normalize_host_url(&host.url) {
+                    let init_script = match normalize_host_init_script(host.init_script.as_deref()) {
+                        Ok(script) => script,
+                        Err(err) => {
+                            log::warn!("[desktop] ignoring init script for host {}: {err}", host.id);
+                            None
+                        }
+                    };
                     hosts.push(DesktopHost {
                         id: host.id,
                         label: if host.label.trim().is_empty() {
@@ -1128,6 +2068,7 @@ fn read_desktop_hosts_config_from_disk() -> DesktopHostsConfig {
                             host.label
                         },
                         url,
+                        init_script,
                     });
                 }
             }
@@ -1141,85 +2082,218 @@ fn read_desktop_hosts_config_from_disk() -> DesktopHostsConfig {
 }
 
 fn read_desktop_window_state_from_disk() -> Option<DesktopWindowState> {
-    let path = settings_file_path();
-    let raw = fs::read_to_string(path).ok();
-    let parsed = raw
-        .as_deref()
-        .and_then(|s| serde_json::from_str::<serde_json::Value>(s).ok());
-
-    parsed
-        .as_ref()
-        .and_then(|v| v.get("desktopWindowState"))
-        .cloned()
+    read_settings_value("desktopWindowState")
         .and_then(|v| serde_json::from_value::<DesktopWindowState>(v).ok())
 }
 
-fn write_desktop_window_state_to_disk(state: &DesktopWindowState) -> Result<()> {
-    let path = settings_file_path();
-    if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent)?;
-    }
+/// Per-window state keyed by workspace identity (`desktopWindowStates`).
+fn read_desktop_window_states_from_disk() -> HashMap<String, DesktopWindowState> {
+    read_settings_value("desktopWindowStates")
+        .and_then(|v| serde_json::from_value::<HashMap<String, DesktopWindowState>>(v).ok())
+        .unwrap_or_default()
+}
 
-    let mut root: serde_json::Value = if let Ok(raw) = fs::read_to_string(&path) {
-        serde_json::from_str(&raw).unwrap_or(serde_json::json!({}))
-    } else {
-        serde_json::json!({})
-    };
+/// Store `state` under `identity` in `desktopWindowStates`. While maximized or
+/// fullscreen the stored normal geometry is kept, so leaving that mode after a
+/// relaunch restores the old frame.
+fn apply_desktop_window_state_for_identity(
+    root: &mut serde_json::Map<String, serde_json::Value>,
+    identity: &str,
+    state: &DesktopWindowState,
+) {
+    let entry = root
+        .entry("desktopWindowStates")
+        .or_insert_with(|| serde_json::json!({}));
+    if !entry.is_object() {
+        *entry = serde_json::json!({});
+    }
 
-    if !root.is_object() {
-        root = serde_json::json!({});
+    let mut next = state.clone();
+    if next.maximized || next.fullscreen {
+        let previous = entry
+            .get(identity)
+            .cloned()
+            .and_then(|v| serde_json::from_value::<DesktopWindowState>(v).ok());
+        if let Some(previous) = previous {
+            next.x = previous.x;
+            next.y = previous.y;
+            next.width = previous.width;
+            next.height = previous.height;
+        }
     }
+    entry[identity] = serde_json::to_value(&next).unwrap_or(serde_json::Value::Null);
+}
 
-    root["desktopWindowState"] = serde_json::to_value(state).unwrap_or(serde_json::Value::Null);
-    fs::write(&path, serde_json::to_string_pretty(&root)?)?;
-    Ok(())
+/// Whether windows from the last session should be reopened on launch (default on).
+fn read_desktop_restore_windows_from_disk() -> bool {
+    read_settings_bool("desktopRestoreWindows", true)
 }
 
-fn write_desktop_hosts_config_to_disk(config: &DesktopHostsConfig) -> Result<()> {
-    let path = settings_file_path();
-    if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent)?;
+/// Target host for New Window, from `desktopNewWindowHost`.
+enum NewWindowHost {
+    /// The configured default host (or `OPENCHAMBER_SERVER_URL`).
+    Default,
+    /// The host of the focused window.
+    Follow,
+    /// A fixed host id.
+    Pinned(String),
+}
+
+fn read_desktop_new_window_host_from_disk() -> NewWindowHost {
+    let value = read_settings_value("desktopNewWindowHost");
+    match value.as_ref().and_then(|v| v.as_str()).map(str::trim).unwrap_or("") {
+        "" | "default" => NewWindowHost::Default,
+        "follow" => NewWindowHost::Follow,
+        host_id => NewWindowHost::Pinned(host_id.to_string()),
     }
+}
 
-    let mut root: serde_json::Value = if let Ok(raw) = fs::read_to_string(&path) {
-        serde_json::from_str(&raw).unwrap_or(serde_json::json!({}))
-    } else {
-        serde_json::json!({})
-    };
+/// `desktopShowWhenReady`: keep new windows hidden until the page reports its first
+/// render via `desktop_window_ready` (or `WINDOW_READY_TIMEOUT` passes).
+fn read_desktop_show_when_ready_from_disk() -> bool {
+    read_settings_bool("desktopShowWhenReady", false)
+}
+
+fn write_desktop_restore_windows_to_disk(enabled: bool) -> Result<()> {
+    update_settings_file(|root| {
+        root.insert("desktopRestoreWindows".into(), serde_json::Value::Bool(enabled));
+    })
+}
+
+/// Whether a default host that needs credentials should fall back to local at startup
+/// instead of opening at its auth wall (default off).
+fn read_desktop_auth_fallback_from_disk() -> bool {
+    read_settings_bool("desktopAuthFallbackToLocal", false)
+}
+
+/// Whether release builds should leave the native context menu alone everywhere
+/// (`desktopNativeContextMenu`, default off). Read when a window's init script is built.
+fn read_desktop_native_context_menu_from_disk() -> bool {
+    read_settings_bool("desktopNativeContextMenu", false)
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum TitleBarMode {
+    /// Content extends under a transparent title bar (the default).
+    #[default]
+    Overlay,
+    /// Standard opaque title bar above the content.
+    Visible,
+}
 
-    if !root.is_object() {
-        root = serde_json::json!({});
+#[derive(Clone, Copy, Serialize, Deserialize)]
+struct TrafficLightInset {
+    x: f64,
+    y: f64,
+}
+
+/// macOS title bar settings (`desktopTitleBarStyle`, `desktopTrafficLightInset`),
+/// applied to each window at creation.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DesktopTitleBarConfig {
+    style: TitleBarMode,
+    traffic_light_inset: TrafficLightInset,
+}
+
+fn clamp_traffic_light_inset(inset: TrafficLightInset) -> TrafficLightInset {
+    let clamp = |value: f64, fallback: f64| {
+        if value.is_finite() {
+            value.clamp(0.0, MAX_TRAFFIC_LIGHT_INSET)
+        } else {
+            fallback
+        }
+    };
+    TrafficLightInset {
+        x: clamp(inset.x, DEFAULT_TRAFFIC_LIGHT_INSET.x),
+        y: clamp(inset.y, DEFAULT_TRAFFIC_LIGHT_INSET.y),
     }
+}
 
-    let hosts: Vec<DesktopHost> = config
-        .hosts
-        .iter()
-        .filter_map(|h| {
-            let id = h.id.trim();
-            if id.is_empty() || id == LOCAL_HOST_ID {
-                return None;
-            }
-            let url = normalize_host_url(&h.url)?;
-            Some(DesktopHost {
-                id: id.to_string(),
-                label: if h.label.trim().is_empty() {
-                    url.clone()
-                } else {
-                    h.label.trim().to_string()
-                },
-                url,
-            })
-        })
-        .collect();
+fn read_desktop_title_bar_from_disk() -> DesktopTitleBarConfig {
+    let settings = read_settings_snapshot();
+    let style = settings
+        .as_ref()
+        .and_then(|v| v.get("desktopTitleBarStyle"))
+        .cloned()
+        .and_then(|v| serde_json::from_value::<TitleBarMode>(v).ok())
+        .unwrap_or_default();
+    let traffic_light_inset = settings
+        .as_ref()
+        .and_then(|v| v.get("desktopTrafficLightInset"))
+        .cloned()
+        .and_then(|v| serde_json::from_value::<TrafficLightInset>(v).ok())
+        .map(clamp_traffic_light_inset)
+        .unwrap_or(DEFAULT_TRAFFIC_LIGHT_INSET);
+
+    DesktopTitleBarConfig { style, traffic_light_inset }
+}
+
+fn write_desktop_title_bar_to_disk(config: &DesktopTitleBarConfig) -> Result<()> {
+    let inset = clamp_traffic_light_inset(config.traffic_light_inset);
+    update_settings_file(|root| {
+        root.insert(
+            "desktopTitleBarStyle".into(),
+            serde_json::to_value(config.style).unwrap_or(serde_json::Value::Null),
+        );
+        root.insert(
+            "desktopTrafficLightInset".into(),
+            serde_json::to_value(inset).unwrap_or(serde_json::Value::Null),
+        );
+    })
+}
+
+/// Whether closing the last window asks for confirmation first (`desktopConfirmQuit`,
+/// default off).
+fn read_desktop_confirm_quit_from_disk() -> bool {
+    read_settings_bool("desktopConfirmQuit", false)
+}
+
+fn read_desktop_session_windows_from_disk() -> Vec<DesktopSessionWindow> {
+    read_settings_value("desktopSessionWindows")
+        .and_then(|v| serde_json::from_value::<Vec<DesktopSessionWindow>>(v).ok())
+        .unwrap_or_default()
+}
+
+fn write_desktop_session_windows_to_disk(windows: &[DesktopSessionWindow]) -> Result<()> {
+    update_settings_file(|root| {
+        root.insert("desktopSessionWindows".into(), serde_json::to_value(windows).unwrap_or(serde_json::Value::Array(vec![])));
+    })
+}
+
+fn write_desktop_hosts_config_to_disk(config: &DesktopHostsConfig) -> Result<()> {
+    let mut used_labels: HashSet<String> = HashSet::new();
+    let mut hosts: Vec<DesktopHost> = Vec::new();
+    for h in &config.hosts {
+        let id = h.id.trim();
+        if id.is_empty() || id == LOCAL_HOST_ID {
+            continue;
+        }
+        let Some(url) = normalize_host_url(&h.url) else {
+            continue;
+        };
+        let label = normalize_host_label(&h.label);
+        hosts.push(DesktopHost {
+            id: id.to_string(),
+            label: dedupe_host_label(if label.is_empty() { url.clone() } else { label }, &mut used_labels),
+            url,
+            init_script: normalize_host_init_script(h.init_script.as_deref())?,
+        });
+    }
 
-    root["desktopHosts"] = serde_json::to_value(hosts).unwrap_or(serde_json::Value::Array(vec![]));
-    root["desktopDefaultHostId"] = match &config.default_host_id {
+    let default_host_id = match &config.default_host_id {
         Some(id) if !id.trim().is_empty() => serde_json::Value::String(id.trim().to_string()),
         _ => serde_json::Value::Null,
     };
 
-    fs::write(&path, serde_json::to_string_pretty(&root)?)?;
-    Ok(())
+    update_settings_file(|root| {
+        root.insert(
+            "desktopHosts".into(),
+            serde_json::to_value(hosts).unwrap_or(serde_json::Value::Array(vec![])),
+        );
+        root.insert("desktopDefaultHostId".into(), default_host_id);
+    })
 }
 
 #[tauri::command]
@@ -1233,52 +2307,343 @@ fn desktop_hosts_set(config: DesktopHostsConfig) -> Result<(), String> {
 }
 
 
-#[derive(Clone, Serialize)]
-#[serde(rename_all = "camelCase")]
-struct HostProbeResult {
-    status: String,
-    latency_ms: u64,
+#[tauri::command]
+fn desktop_title_bar_get() -> Result<DesktopTitleBarConfig, String> {
+    Ok(read_desktop_title_bar_from_disk())
 }
 
+/// Persist the title bar settings and switch the style on every open window.
+///
+/// Tauri only exposes the traffic-light position on the window builder, so a new
+/// inset takes effect for windows opened afterwards.
 #[tauri::command]
-async fn desktop_host_probe(url: String) -> Result<HostProbeResult, String> {
-    let normalized = normalize_host_url(&url).ok_or_else(|| "Invalid URL".to_string())?;
-    let health = format!("{}/health", normalized.trim_end_matches('/'));
-    let client = reqwest::Client::builder()
-        .no_proxy()
-        .timeout(Duration::from_secs(2))
-        .build()
-        .map_err(|err| err.to_string())?;
-    let started = std::time::Instant::now();
+fn desktop_title_bar_set(app: tauri::AppHandle, config: DesktopTitleBarConfig) -> Result<(), String> {
+    write_desktop_title_bar_to_disk(&config).map_err(|err| err.to_string())?;
 
-    match client.get(&health).send().await {
-        Ok(resp) => {
-            let status = resp.status();
-            let latency_ms = started.elapsed().as_millis() as u64;
-            if status.is_success() {
-                Ok(HostProbeResult {
-                    status: "ok".to_string(),
-                    latency_ms,
-                })
-            } else if status.as_u16() == 401 || status.as_u16() == 403 {
-                Ok(HostProbeResult {
-                    status: "auth".to_string(),
+    #[cfg(target_os = "macos")]
+    {
+        let style = match config.style {
+            TitleBarMode::Overlay => tauri::TitleBarStyle::Overlay,
+            TitleBarMode::Visible => tauri::TitleBarStyle::Visible,
+        };
+        for window in app.webview_windows().values() {
+            if let Err(err) = window.set_title_bar_style(style) {
+                log::warn!("[desktop] failed to set title bar style on {}: {err}", window.label());
+            }
+        }
+    }
+    #[cfg(not(target_os = "macos"))]
+    let _ = app;
+
+    Ok(())
+}
+
+#[derive(Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CompactSettingsReport {
+    backup_path: Option<String>,
+    /// Paths of projects removed because they no longer exist.
+    pruned_projects: Vec<String>,
+    cleared_active_project: bool,
+    /// Hosts removed because their URL is invalid (id, or the raw entry if it had none).
+    pruned_hosts: Vec<String>,
+    cleared_default_host: bool,
+}
+
+/// Expand a leading `~` the way stored project paths may use it.
+fn expand_home_path(raw: &str) -> PathBuf {
+    let home = || env::var_os(if cfg!(windows) { "USERPROFILE" } else { "HOME" });
+    if raw == "~" {
+        if let Some(home) = home() {
+            return PathBuf::from(home);
+        }
+    }
+    if let Some(rest) = raw.strip_prefix("~/") {
+        if let Some(home) = home() {
+            return PathBuf::from(home).join(rest);
+        }
+    }
+    PathBuf::from(raw)
+}
+
+fn compact_settings_root(root: &mut serde_json::Map<String, serde_json::Value>) -> CompactSettingsReport {
+    let mut report = CompactSettingsReport::default();
+
+    if let Some(serde_json::Value::Array(projects)) = root.get_mut("projects") {
+        projects.retain(|project| {
+            let path = project.get("path").and_then(|v| v.as_str()).map(str::trim).unwrap_or("");
+            let keep = !path.is_empty() && expand_home_path(path).exists();
+            if !keep {
+                report.pruned_projects.push(path.to_string());
+            }
+            keep
+        });
+    }
+    let project_ids: HashSet<String> = root
+        .get("projects")
+        .and_then(|v| v.as_array())
+        .map(|projects| {
+            projects
+                .iter()
+                .filter_map(|project| project.get("id").and_then(|v| v.as_str()).map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default();
+    let active_is_stale = root
+        .get("activeProjectId")
+        .and_then(|v| v.as_str())
+        .is_some_and(|id| !project_ids.contains(id));
+    if active_is_stale {
+        root.remove("activeProjectId");
+        report.cleared_active_project = true;
+    }
+
+    if let Some(serde_json::Value::Array(hosts)) = root.get_mut("desktopHosts") {
+        hosts.retain(|host| {
+            let valid = host
+                .get("url")
+                .and_then(|v| v.as_str())
+                .and_then(normalize_host_url)
+                .is_some();
+            if !valid {
+                let id = host.get("id").and_then(|v| v.as_str()).map(str::to_string);
+                report.pruned_hosts.push(id.unwrap_or_else(|| host.to_string()));
+            }
+            valid
+        });
+    }
+    let host_ids: HashSet<String> = root
+        .get("desktopHosts")
+        .and_then(|v| v.as_array())
+        .map(|hosts| {
+            hosts
+                .iter()
+                .filter_map(|host| host.get("id").and_then(|v| v.as_str()).map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default();
+    let default_is_stale = root
+        .get("desktopDefaultHostId")
+        .and_then(|v| v.as_str())
+        .is_some_and(|id| id != LOCAL_HOST_ID && !host_ids.contains(id));
+    if default_is_stale {
+        root.insert("desktopDefaultHostId".into(), serde_json::Value::Null);
+        report.cleared_default_host = true;
+    }
+
+    report
+}
+
+/// Back up settings.json, then drop projects whose paths are gone, hosts with
+/// invalid URLs, and ids that pointed at either. The file is rewritten pretty-printed.
+#[tauri::command]
+fn desktop_compact_settings() -> Result<CompactSettingsReport, String> {
+    try_update_settings_file(|path, root| {
+        let mut backup_path = None;
+        if path.exists() {
+            let backup = settings_backup_path(path, "bak");
+            fs::copy(path, &backup).map_err(|err| anyhow!("Failed to back up settings.json: {err}"))?;
+            backup_path = Some(backup.to_string_lossy().into_owned());
+        }
+
+        let mut report = compact_settings_root(root);
+        report.backup_path = backup_path;
+        log::info!(
+            "[desktop] compacted settings: {} project(s), {} host(s) pruned",
+            report.pruned_projects.len(),
+            report.pruned_hosts.len()
+        );
+        Ok(report)
+    })
+    .map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+fn desktop_restore_windows_get() -> Result<bool, String> {
+    Ok(read_desktop_restore_windows_from_disk())
+}
+
+#[tauri::command]
+fn desktop_restore_windows_set(enabled: bool) -> Result<(), String> {
+    write_desktop_restore_windows_to_disk(enabled).map_err(|err| err.to_string())
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct HostProbeResult {
+    status: String,
+    latency_ms: u64,
+    /// Why an `unreachable` probe failed: `dns`, `refused`, `tls`, `timeout` or `other`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reason: Option<String>,
+}
+
+/// Classify a failed probe request from `reqwest`'s flags and its source chain.
+fn classify_probe_error(err: &reqwest::Error) -> &'static str {
+    if err.is_timeout() {
+        return "timeout";
+    }
+
+    let mut source: Option<&(dyn std::error::Error + 'static)> = Some(err);
+    while let Some(current) = source {
+        if let Some(io) = current.downcast_ref::<std::io::Error>() {
+            match io.kind() {
+                std::io::ErrorKind::ConnectionRefused => return "refused",
+                std::io::ErrorKind::TimedOut => return "timeout",
+                _ => {}
+            }
+        }
+        let message = current.to_string().to_ascii_lowercase();
+        if message.contains("dns error") || message.contains("failed to lookup address") {
+            return "dns";
+        }
+        if message.contains("certificate") || message.contains("tls") || message.contains("ssl") {
+            return "tls";
+        }
+        source = current.source();
+    }
+    "other"
+}
+
+#[tauri::command]
+async fn desktop_host_probe(url: String) -> Result<HostProbeResult, String> {
+    let normalized = normalize_host_url(&url).ok_or_else(|| "Invalid URL".to_string())?;
+    let health = format!("{}/health", normalized.trim_end_matches('/'));
+    let client = reqwest::Client::builder()
+        .no_proxy()
+        .timeout(Duration::from_secs(2))
+        .build()
+        .map_err(|err| err.to_string())?;
+    let started = std::time::Instant::now();
+
+    match client.get(&health).send().await {
+        Ok(resp) => {
+            let status = resp.status();
+            let latency_ms = started.elapsed().as_millis() as u64;
+            if status.is_success() {
+                Ok(HostProbeResult {
+                    status: "ok".to_string(),
+                    latency_ms,
+                    reason: None,
+                })
+            } else if status.as_u16() == 401 || status.as_u16() == 403 {
+                Ok(HostProbeResult {
+                    status: "auth".to_string(),
                     latency_ms,
+                    reason: None,
                 })
             } else {
                 Ok(HostProbeResult {
                     status: "unreachable".to_string(),
                     latency_ms,
+                    reason: Some("other".to_string()),
                 })
             }
         }
-        Err(_) => Ok(HostProbeResult {
+        Err(err) => Ok(HostProbeResult {
             status: "unreachable".to_string(),
             latency_ms: started.elapsed().as_millis() as u64,
+            reason: Some(classify_probe_error(&err).to_string()),
         }),
     }
 }
 
+/// Probe local plus every configured host concurrently, keyed by host id.
+/// Also refreshes the unreachable-host cache used by `open_new_window`.
+#[tauri::command]
+async fn desktop_host_probe_all(app: tauri::AppHandle) -> Result<HashMap<String, HostProbeResult>, String> {
+    let mut targets: Vec<(String, String)> = Vec::new();
+    let local_url = app
+        .try_state::<SidecarState>()
+        .and_then(|state| state.url.lock().expect("sidecar url mutex").clone());
+    if let Some(local_url) = local_url {
+        targets.push((LOCAL_HOST_ID.to_string(), local_url));
+    }
+    for host in read_desktop_hosts_config_from_disk().hosts {
+        targets.push((host.id, host.url));
+    }
+
+    // Spawn every probe up front so the per-host timeouts overlap.
+    let handles: Vec<_> = targets
+        .into_iter()
+        .map(|(id, url)| {
+            tauri::async_runtime::spawn(async move {
+                let result = desktop_host_probe(url.clone()).await;
+                (id, url, result)
+            })
+        })
+        .collect();
+
+    let mut results: HashMap<String, HostProbeResult> = HashMap::new();
+    for handle in handles {
+        let Ok((id, url, result)) = handle.await else {
+            continue;
+        };
+        let probe = match result {
+            Ok(probe) => probe,
+            Err(err) => {
+                log::warn!("[desktop] host probe failed ({}): {}", url, err);
+                continue;
+            }
+        };
+
+        if id != LOCAL_HOST_ID {
+            if let Some(state) = app.try_state::<DesktopUiInjectionState>() {
+                let mut guard = state.auth_required_hosts.lock().expect("auth required hosts mutex");
+                if probe.status == "auth" {
+                    guard.insert(url.clone());
+                } else {
+                    guard.remove(&url);
+                }
+                drop(guard);
+
+                let mut guard = state.unreachable_hosts.lock().expect("unreachable hosts mutex");
+                if probe.status == "unreachable" {
+                    guard.insert(url);
+                } else {
+                    guard.remove(&url);
+                }
+            }
+        }
+
+        results.insert(id, probe);
+    }
+
+    Ok(results)
+}
+
+/// Forget every host cached as unreachable so new windows try them again.
+/// Returns how many entries were dropped.
+#[tauri::command]
+fn desktop_clear_unreachable_hosts(app: tauri::AppHandle) -> Result<usize, String> {
+    let state = app
+        .try_state::<DesktopUiInjectionState>()
+        .ok_or_else(|| "Desktop state unavailable".to_string())?;
+    let mut guard = state.unreachable_hosts.lock().expect("unreachable hosts mutex");
+    let cleared = guard.len();
+    guard.clear();
+    if cleared > 0 {
+        log::info!("[desktop] cleared {cleared} unreachable host(s)");
+    }
+    Ok(cleared)
+}
+
+/// Drop one host from the unreachable cache, e.g. after a successful manual probe.
+/// Returns whether it was cached.
+#[tauri::command]
+fn desktop_mark_host_reachable(app: tauri::AppHandle, url: String) -> Result<bool, String> {
+    let host_url = normalize_host_url(&url).ok_or_else(|| "Invalid host URL".to_string())?;
+    let state = app
+        .try_state::<DesktopUiInjectionState>()
+        .ok_or_else(|| "Desktop state unavailable".to_string())?;
+    let removed = state
+        .unreachable_hosts
+        .lock()
+        .expect("unreachable hosts mutex")
+        .remove(&host_url);
+    Ok(removed)
+}
+
 #[derive(Clone, Serialize)]
 #[serde(tag = "event", content = "data")]
 enum UpdateProgressEvent {
@@ -1295,6 +2660,20 @@ enum UpdateProgressEvent {
     Finished,
 }
 
+/// `openchamber:update-progress` payload: the progress event tagged with the id of
+/// the install it belongs to, so windows can ignore other windows' installs.
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct UpdateProgressPayload {
+    install_id: String,
+    #[serde(flatten)]
+    event: UpdateProgressEvent,
+}
+
+/// Set while an update is downloading/installing; only one install may run at a time.
+static UPDATE_INSTALL_IN_FLIGHT: AtomicBool = AtomicBool::new(false);
+static UPDATE_INSTALL_COUNTER: AtomicU64 = AtomicU64::new(1);
+
 #[derive(Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 struct DesktopUpdateInfo {
@@ -1318,6 +2697,9 @@ fn is_nonempty_string(value: &str) -> bool {
 }
 
 const CHANGELOG_URL: &str = "https://raw.githubusercontent.com/btriapitsyn/openchamber/main/CHANGELOG.md";
+const CHANGELOG_FETCH_ATTEMPTS: u32 = 3;
+/// Delay before retry N is N times this.
+const CHANGELOG_FETCH_BACKOFF: Duration = Duration::from_millis(500);
 
 fn parse_semver_num(value: &str) -> Option<u32> {
     let trimmed = value.trim().trim_start_matches('v');
@@ -1341,6 +2723,14 @@ fn is_placeholder_release_notes(body: &Option<String>) -> bool {
         .starts_with("see release notes at")
 }
 
+async fn fetch_changelog_text(client: &reqwest::Client) -> Result<String, String> {
+    let response = client.get(CHANGELOG_URL).send().await.map_err(|err| err.to_string())?;
+    if !response.status().is_success() {
+        return Err(format!("HTTP {}", response.status()));
+    }
+    response.text().await.map_err(|err| err.to_string())
+}
+
 async fn fetch_changelog_notes(from_version: &str, to_version: &str) -> Option<String> {
     let from_num = parse_semver_num(from_version)?;
     let to_num = parse_semver_num(to_version)?;
@@ -1354,11 +2744,22 @@ async fn fetch_changelog_notes(from_version: &str, to_version: &str) -> Option<S
         .build()
         .ok()?;
 
-    let response = client.get(CHANGELOG_URL).send().await.ok()?;
-    if !response.status().is_success() {
-        return None;
+    let mut changelog = None;
+    for attempt in 1..=CHANGELOG_FETCH_ATTEMPTS {
+        match fetch_changelog_text(&client).await {
+            Ok(text) => {
+                changelog = Some(text);
+                break;
+            }
+            Err(err) => {
+                log::warn!("[desktop] changelog fetch attempt {attempt}/{CHANGELOG_FETCH_ATTEMPTS} failed: {err}");
+                if attempt < CHANGELOG_FETCH_ATTEMPTS {
+                    tokio::time::sleep(CHANGELOG_FETCH_BACKOFF * attempt).await;
+                }
+            }
+        }
     }
-    let changelog = response.text().await.ok()?;
+    let changelog = changelog?;
     if changelog.trim().is_empty() {
         return None;
     }
@@ -1408,6 +2809,33 @@ async fn fetch_changelog_notes(from_version: &str, to_version: &str) -> Option<S
     }
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ReleaseNotesResult {
+    /// False when `to_version` is not newer than `from_version` or no sections matched.
+    available: bool,
+    notes: Option<String>,
+}
+
+/// Fetch changelog sections between two versions without running an update check.
+#[tauri::command]
+async fn desktop_get_release_notes(from_version: String, to_version: String) -> Result<ReleaseNotesResult, String> {
+    let from_num = parse_semver_num(&from_version).ok_or_else(|| format!("Invalid version: {from_version}"))?;
+    let to_num = parse_semver_num(&to_version).ok_or_else(|| format!("Invalid version: {to_version}"))?;
+    if to_num <= from_num {
+        return Ok(ReleaseNotesResult {
+            available: false,
+            notes: None,
+        });
+    }
+
+    let notes = fetch_changelog_notes(&from_version, &to_version).await;
+    Ok(ReleaseNotesResult {
+        available: notes.is_some(),
+        notes,
+    })
+}
+
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct SidecarNotifyPayload {
@@ -1438,16 +2866,57 @@ fn maybe_show_sidecar_notification(app: &tauri::AppHandle, payload: SidecarNotif
 
     use tauri_plugin_notification::NotificationExt;
 
-    let mut builder = app.notification().builder().title(title);
-    if let Some(body) = body {
-        builder = builder.body(body);
+    match app.notification().permission_state() {
+        Ok(tauri::plugin::PermissionState::Granted) => {}
+        Ok(state) => log::warn!("[desktop] showing sidecar notification without permission (state={state})"),
+        Err(err) => log::warn!("[desktop] could not read notification permission: {err}"),
     }
 
-    #[cfg(target_os = "macos")]
-    {
-        builder = builder.sound("Glass");
+    let _ = show_desktop_notification(app, title, body);
+}
+
+const SIDECAR_NOTIFY_ERROR_PREVIEW_CHARS: usize = 200;
+
+/// Log a notify line that failed to parse; debug builds also emit
+/// `openchamber:sidecar-notify-error` so server developers can see it in the UI.
+fn report_sidecar_notify_parse_error(app: &tauri::AppHandle, raw: &str, err: &serde_json::Error) {
+    let raw = raw.trim();
+    let mut preview: String = raw.chars().take(SIDECAR_NOTIFY_ERROR_PREVIEW_CHARS).collect();
+    if preview.len() < raw.len() {
+        preview.push('…');
+    }
+    log::warn!(target: SIDECAR_LOG_TARGET, "event=notify_parse_failed error={err} payload={preview}");
+
+    if cfg!(debug_assertions) {
+        let _ = app.emit(
+            "openchamber:sidecar-notify-error",
+            serde_json::json!({ "error": err.to_string(), "payload": preview }),
+        );
     }
-    let _ = builder.show();
+}
+
+/// Total time to wait for a server to become healthy: `OPENCHAMBER_HEALTH_TIMEOUT_SECS`,
+/// then `desktopHealthTimeoutSecs` in settings, else `HEALTH_TIMEOUT`.
+fn health_timeout() -> Duration {
+    let from_env = env::var("OPENCHAMBER_HEALTH_TIMEOUT_SECS")
+        .ok()
+        .and_then(|raw| raw.trim().parse::<u64>().ok());
+    let from_settings = || read_settings_value("desktopHealthTimeoutSecs")?.as_u64();
+
+    from_env
+        .or_else(from_settings)
+        .filter(|secs| *secs > 0)
+        .map(Duration::from_secs)
+        .unwrap_or(HEALTH_TIMEOUT)
+}
+
+/// Scale `interval` by a pseudo-random factor in [0.8, 1.2) so pollers don't align.
+fn with_jitter(interval: Duration) -> Duration {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|value| value.subsec_nanos())
+        .unwrap_or(0);
+    interval.mul_f64(0.8 + (nanos % 400) as f64 / 1000.0)
 }
 
 async fn wait_for_health(url: &str) -> bool {
@@ -1456,16 +2925,32 @@ async fn wait_for_health(url: &str) -> bool {
         Err(_) => return false,
     };
 
-    let deadline = std::time::Instant::now() + HEALTH_TIMEOUT;
+    let deadline = std::time::Instant::now() + health_timeout();
     let health_url = format!("{}/health", url.trim_end_matches('/'));
+    let mut interval = HEALTH_POLL_INTERVAL;
+    let mut successes = 0;
 
     while std::time::Instant::now() < deadline {
-        if let Ok(resp) = client.get(&health_url).send().await {
-            if resp.status().is_success() {
+        let healthy = match client.get(&health_url).send().await {
+            Ok(resp) => resp.status().is_success(),
+            Err(_) => false,
+        };
+
+        if healthy {
+            successes += 1;
+            if successes >= HEALTH_REQUIRED_SUCCESSES {
                 return true;
             }
+            // Confirm quickly once the server starts answering.
+            interval = HEALTH_POLL_INTERVAL;
+        } else {
+            successes = 0;
+        }
+
+        tokio::time::sleep(with_jitter(interval)).await;
+        if !healthy {
+            interval = interval.mul_f64(1.5).min(HEALTH_POLL_MAX_INTERVAL);
         }
-        tokio::time::sleep(HEALTH_POLL_INTERVAL).await;
     }
 
     false
@@ -1482,23 +2967,128 @@ fn kill_sidecar(app: tauri::AppHandle) {
     }
 }
 
-fn build_local_url(port: u16) -> String {
-    format!("http://127.0.0.1:{port}")
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SidecarStatus {
+    running: bool,
+    pid: Option<u32>,
+    url: Option<String>,
+    port: Option<u16>,
+    /// Unix time in milliseconds the current process was spawned.
+    started_at: Option<u64>,
+    uptime_secs: Option<u64>,
+    last_health: Option<SidecarHealthCheck>,
 }
 
-async fn spawn_local_server(app: &tauri::AppHandle) -> Result<String> {
-    let stored_port = read_desktop_local_port_from_disk();
-    let mut candidates: Vec<Option<u16>> = Vec::new();
-    if let Some(port) = stored_port {
-        candidates.push(Some(port));
+/// Live sidecar state for diagnostics. Only sends a health request when `check` is
+/// true; otherwise `lastHealth` is the most recent recorded result.
+#[tauri::command]
+async fn desktop_sidecar_status(app: tauri::AppHandle, check: Option<bool>) -> Result<SidecarStatus, String> {
+    let state = app
+        .try_state::<SidecarState>()
+        .ok_or_else(|| "Sidecar state unavailable".to_string())?;
+
+    let url = state.url.lock().expect("sidecar url mutex").clone();
+    let pid = state.child.lock().expect("sidecar mutex").as_ref().map(|child| child.pid());
+
+    if check.unwrap_or(false) {
+        if let Some(url) = url.as_deref() {
+            let health_url = format!("{}/health", url.trim_end_matches('/'));
+            let healthy = match reqwest::Client::builder()
+                .no_proxy()
+                .timeout(Duration::from_secs(3))
+                .build()
+            {
+                Ok(client) => client
+                    .get(&health_url)
+                    .send()
+                    .await
+                    .map(|resp| resp.status().is_success())
+                    .unwrap_or(false),
+                Err(_) => false,
+            };
+            state.record_health(healthy);
+        }
     }
-    candidates.push(Some(DEFAULT_DESKTOP_PORT));
-    candidates.push(None);
 
-    let dist_dir = resolve_web_dist_dir(app)?;
-    let no_proxy = "localhost,127.0.0.1";
+    let started = *state.started_at.lock().expect("sidecar started mutex");
+    let started_at = started
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|value| value.as_millis() as u64);
+    let uptime_secs = started
+        .filter(|_| pid.is_some())
+        .and_then(|time| time.elapsed().ok())
+        .map(|elapsed| elapsed.as_secs());
+    let port = url
+        .as_deref()
+        .and_then(|raw| url::Url::parse(raw).ok())
+        .and_then(|parsed| parsed.port_or_known_default());
+    let last_health = state.last_health.lock().expect("sidecar health mutex").clone();
+
+    Ok(SidecarStatus {
+        running: pid.is_some(),
+        pid,
+        url,
+        port,
+        started_at,
+        uptime_secs,
+        last_health,
+    })
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ResolvedHome {
+    home_dir: Option<String>,
+    /// `HOME` from the app's own environment, which a macOS GUI launch may leave unset.
+    env_home: Option<String>,
+    path_segments: Vec<String>,
+}
+
+/// Home directory and PATH segments the sidecar was spawned with. Before the first
+/// spawn, `homeDir` is resolved on the spot and `pathSegments` is empty.
+#[tauri::command]
+fn desktop_get_resolved_home(app: tauri::AppHandle) -> ResolvedHome {
+    let (home, path) = match app.try_state::<SidecarState>() {
+        Some(state) => (
+            state.home.lock().expect("sidecar home mutex").clone(),
+            state.path.lock().expect("sidecar path mutex").clone(),
+        ),
+        None => (None, None),
+    };
+    let home_dir = home.or_else(|| {
+        app.path()
+            .home_dir()
+            .ok()
+            .map(|dir| dir.to_string_lossy().to_string())
+            .filter(|dir| is_nonempty_string(dir))
+    });
+
+    ResolvedHome {
+        home_dir,
+        env_home: env::var("HOME").ok(),
+        path_segments: path
+            .map(|value| value.split(':').map(str::to_string).collect())
+            .unwrap_or_default(),
+    }
+}
+
+fn build_local_url(port: u16) -> String {
+    format!("http://127.0.0.1:{port}")
+}
+
+/// Home dir, `opencodeBinary` setting and PATH segments the sidecar is spawned with.
+struct SidecarSearchPath {
+    home_dir: Option<String>,
+    /// `opencodeBinary` from settings (a directory is resolved to the binary inside).
+    opencode_binary: Option<String>,
+    segments: Vec<String>,
+}
 
-    // macOS app launch env often lacks user PATH entries.
+/// Build the sidecar's PATH: explicit binary overrides first, then common install
+/// locations and home-relative dirs, then the inherited PATH. macOS app launch env
+/// often lacks user PATH entries.
+fn resolve_sidecar_search_path(app: &tauri::AppHandle) -> SidecarSearchPath {
     let mut path_segments: Vec<String> = Vec::new();
     let mut seen = std::collections::HashSet::<String>::new();
 
@@ -1611,33 +3201,289 @@ async fn spawn_local_server(app: &tauri::AppHandle) -> Result<String> {
         }
     }
 
-    let augmented_path = path_segments.join(":");
 
-    for candidate in candidates {
-        let port = match candidate {
-            Some(p) => p,
-            None => pick_unused_port()?,
-        };
-        let url = build_local_url(port);
+    SidecarSearchPath {
+        home_dir: resolved_home_dir,
+        opencode_binary: opencode_binary_from_settings,
+        segments: path_segments,
+    }
+}
 
-        let mut cmd = app
-            .shell()
-            .sidecar(SIDECAR_NAME)
-            .map_err(|err| anyhow!("Failed to resolve sidecar '{SIDECAR_NAME}': {err}"))?
-            .args(["--port", &port.to_string()])
-            .env("OPENCHAMBER_HOST", "127.0.0.1")
-            .env("OPENCHAMBER_DIST_DIR", dist_dir.clone())
-            .env("OPENCHAMBER_DESKTOP_NOTIFY", "true")
-            .env("PATH", augmented_path.clone())
-            .env("NO_PROXY", no_proxy)
-            .env("no_proxy", no_proxy);
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct OpencodeBinaryResolution {
+    /// The binary the sidecar would use, if one was found.
+    binary: Option<String>,
+    /// Where it came from: the server's own `source` when it answered, otherwise
+    /// `settings`, the env var name, `path` or `fallback`.
+    source: Option<String>,
+    /// `server` when the running server reported it, `desktop` for the local mirror.
+    resolved_by: String,
+    /// First line of `opencode --version`.
+    version: Option<String>,
+    /// Directories searched, in order.
+    searched: Vec<String>,
+}
 
-        if let Some(home) = resolved_home_dir.as_deref() {
-            cmd = cmd.env("HOME", home);
+/// Mirrors the server's `isExecutable`: a regular file with an exec bit, or on
+/// Windows an extensionless file or one of the runnable extensions.
+fn is_executable_file(path: &Path) -> bool {
+    let Ok(meta) = fs::metadata(path) else {
+        return false;
+    };
+    if !meta.is_file() {
+        return false;
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        meta.permissions().mode() & 0o111 != 0
+    }
+    #[cfg(not(unix))]
+    {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            None => true,
+            Some(ext) => ["exe", "cmd", "bat", "com"].contains(&ext.to_ascii_lowercase().as_str()),
         }
+    }
+}
 
-        if let Some(bin) = opencode_binary_from_settings.as_deref() {
-            let trimmed = bin.trim();
+/// Home-relative and system install locations the server checks after PATH.
+fn opencode_fallback_candidates(home: Option<&str>) -> Vec<PathBuf> {
+    if cfg!(windows) {
+        let user_profile = env::var("USERPROFILE").ok().or_else(|| home.map(str::to_string));
+        let app_data = env::var("APPDATA").unwrap_or_default();
+        let local_app_data = env::var("LOCALAPPDATA").unwrap_or_default();
+        let program_data = env::var("ProgramData").unwrap_or_else(|_| "C:\\ProgramData".to_string());
+        let mut candidates = Vec::new();
+        if let Some(profile) = user_profile.as_deref() {
+            let profile = Path::new(profile);
+            candidates.push(profile.join(".opencode").join("bin").join("opencode.exe"));
+            candidates.push(profile.join(".opencode").join("bin").join("opencode.cmd"));
+        }
+        if !app_data.is_empty() {
+            candidates.push(Path::new(&app_data).join("npm").join("opencode.cmd"));
+        }
+        if let Some(profile) = user_profile.as_deref() {
+            candidates.push(Path::new(profile).join("scoop").join("shims").join("opencode.cmd"));
+        }
+        candidates.push(Path::new(&program_data).join("chocolatey").join("bin").join("opencode.exe"));
+        candidates.push(Path::new(&program_data).join("chocolatey").join("bin").join("opencode.cmd"));
+        if let Some(profile) = user_profile.as_deref() {
+            let profile = Path::new(profile);
+            candidates.push(profile.join(".bun").join("bin").join("opencode.exe"));
+            candidates.push(profile.join(".bun").join("bin").join("opencode.cmd"));
+        }
+        if !local_app_data.is_empty() {
+            candidates.push(Path::new(&local_app_data).join("Programs").join("opencode").join("opencode.exe"));
+        }
+        candidates
+    } else {
+        let mut candidates = Vec::new();
+        if let Some(home) = home {
+            let home = Path::new(home);
+            candidates.push(home.join(".opencode").join("bin").join("opencode"));
+            candidates.push(home.join(".bun").join("bin").join("opencode"));
+            candidates.push(home.join(".local").join("bin").join("opencode"));
+            candidates.push(home.join("bin").join("opencode"));
+        }
+        for dir in ["/opt/homebrew/bin", "/usr/local/bin", "/usr/bin", "/bin"] {
+            candidates.push(Path::new(dir).join("opencode"));
+        }
+        candidates
+    }
+}
+
+/// The binary the running server resolved, from `/api/config/opencode-resolution`.
+async fn fetch_server_opencode_resolution(app: &tauri::AppHandle) -> Option<(String, String)> {
+    let url = app
+        .try_state::<SidecarState>()
+        .and_then(|state| state.url.lock().expect("sidecar url mutex").clone())?;
+    let client = reqwest::Client::builder()
+        .no_proxy()
+        .timeout(Duration::from_secs(3))
+        .build()
+        .ok()?;
+    let body = client
+        .get(format!("{}/api/config/opencode-resolution", url.trim_end_matches('/')))
+        .send()
+        .await
+        .ok()?
+        .error_for_status()
+        .ok()?
+        .text()
+        .await
+        .ok()?;
+    let json = serde_json::from_str::<serde_json::Value>(&body).ok()?;
+    let resolved = json.get("resolved")?.as_str()?.trim();
+    if resolved.is_empty() {
+        return None;
+    }
+    let source = json.get("source").and_then(|v| v.as_str()).unwrap_or("server");
+    Some((resolved.to_string(), source.to_string()))
+}
+
+/// Local mirror of the server's `resolveOpencodeCliPath` as the sidecar would see it,
+/// used while the server isn't up: the `opencodeBinary` setting (passed as
+/// `OPENCODE_BINARY`), env overrides, the sidecar PATH, then the home fallbacks.
+fn resolve_opencode_binary_locally(search: &SidecarSearchPath) -> Option<(String, String)> {
+    if let Some(binary) = search.opencode_binary.as_ref().filter(|binary| is_executable_file(Path::new(binary))) {
+        return Some((binary.clone(), "settings".to_string()));
+    }
+    for var in [
+        "OPENCODE_BINARY",
+        "OPENCODE_PATH",
+        "OPENCHAMBER_OPENCODE_PATH",
+        "OPENCHAMBER_OPENCODE_BIN",
+    ] {
+        if let Ok(value) = env::var(var) {
+            let trimmed = value.trim();
+            if !trimmed.is_empty() && is_executable_file(Path::new(trimmed)) {
+                return Some((trimmed.to_string(), var.to_string()));
+            }
+        }
+    }
+    // Like the server's `searchPathFor('opencode')`: the extensionless name on every
+    // platform (npm installs an `opencode` shim next to `opencode.cmd`).
+    let on_path = search
+        .segments
+        .iter()
+        .map(|dir| Path::new(dir).join("opencode"))
+        .find(|candidate| is_executable_file(candidate));
+    if let Some(candidate) = on_path {
+        return Some((candidate.to_string_lossy().to_string(), "path".to_string()));
+    }
+    opencode_fallback_candidates(search.home_dir.as_deref())
+        .into_iter()
+        .find(|candidate| is_executable_file(candidate))
+        .map(|candidate| (candidate.to_string_lossy().to_string(), "fallback".to_string()))
+}
+
+/// First line of `<binary> --version`, killing the process if it takes over 5s.
+fn probe_opencode_version(binary: &str, path: &str) -> Option<String> {
+    use std::io::Read;
+
+    let mut child = Command::new(binary)
+        .arg("--version")
+        .env("PATH", path)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::null())
+        .spawn()
+        .ok()?;
+    let deadline = std::time::Instant::now() + Duration::from_secs(5);
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if std::time::Instant::now() < deadline => std::thread::sleep(Duration::from_millis(50)),
+            _ => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return None;
+            }
+        }
+    };
+    if !status.success() {
+        return None;
+    }
+
+    let mut stdout = String::new();
+    child.stdout.take()?.read_to_string(&mut stdout).ok()?;
+    stdout
+        .lines()
+        .next()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+}
+
+/// Report which opencode binary the server uses: the running server's own answer
+/// when it is up, else the local mirror of its lookup. Includes the version and the
+/// sidecar PATH, for debugging "opencode not found".
+#[tauri::command]
+async fn desktop_resolve_opencode_binary(app: tauri::AppHandle) -> Result<OpencodeBinaryResolution, String> {
+    let search = resolve_sidecar_search_path(&app);
+
+    let (found, resolved_by) = match fetch_server_opencode_resolution(&app).await {
+        Some(found) => (Some(found), "server"),
+        None => (resolve_opencode_binary_locally(&search), "desktop"),
+    };
+
+    let version = match found.as_ref() {
+        Some((binary, _)) => {
+            let binary = binary.clone();
+            let path = search.segments.join(":");
+            tauri::async_runtime::spawn_blocking(move || probe_opencode_version(&binary, &path))
+                .await
+                .ok()
+                .flatten()
+        }
+        None => None,
+    };
+
+    let (binary, source) = found.unzip();
+    Ok(OpencodeBinaryResolution {
+        binary,
+        source,
+        resolved_by: resolved_by.to_string(),
+        version,
+        searched: search.segments,
+    })
+}
+
+async fn spawn_local_server(app: &tauri::AppHandle) -> Result<String> {
+    let stored_port = read_desktop_local_port_from_disk();
+    let mut candidates: Vec<Option<u16>> = Vec::new();
+    if let Some(port) = stored_port {
+        candidates.push(Some(port));
+    }
+    candidates.push(Some(DEFAULT_DESKTOP_PORT));
+    candidates.push(None);
+
+    let dist_dir = resolve_web_dist_dir(app)?;
+    let no_proxy = "localhost,127.0.0.1";
+
+    let SidecarSearchPath {
+        home_dir: resolved_home_dir,
+        opencode_binary: opencode_binary_from_settings,
+        segments: path_segments,
+    } = resolve_sidecar_search_path(app);
+
+    let augmented_path = path_segments.join(":");
+    let extra_args = read_sidecar_args_from_disk();
+    if let Some(state) = app.try_state::<SidecarState>() {
+        *state.path.lock().expect("sidecar path mutex") = Some(augmented_path.clone());
+        *state.home.lock().expect("sidecar home mutex") = resolved_home_dir.clone();
+    }
+
+    for (attempt, candidate) in candidates.into_iter().enumerate() {
+        let port = match candidate {
+            Some(p) => p,
+            None => pick_unused_port()?,
+        };
+        let url = build_local_url(port);
+        let event = if attempt == 0 { "spawn" } else { "respawn" };
+        log::info!(target: SIDECAR_LOG_TARGET, "event={event} attempt={} port={port}", attempt + 1);
+        log::debug!(target: SIDECAR_LOG_TARGET, "event=argv args={:?}", [vec!["--port".to_string(), port.to_string()], extra_args.clone()].concat());
+
+        let mut cmd = app
+            .shell()
+            .sidecar(SIDECAR_NAME)
+            .map_err(|err| anyhow!("Failed to resolve sidecar '{SIDECAR_NAME}': {err}"))?
+            .args(["--port", &port.to_string()])
+            .args(&extra_args)
+            .env("OPENCHAMBER_HOST", "127.0.0.1")
+            .env("OPENCHAMBER_DIST_DIR", dist_dir.clone())
+            .env("OPENCHAMBER_DESKTOP_NOTIFY", "true")
+            .env("PATH", augmented_path.clone())
+            .env("NO_PROXY", no_proxy)
+            .env("no_proxy", no_proxy);
+
+        if let Some(home) = resolved_home_dir.as_deref() {
+            cmd = cmd.env("HOME", home);
+        }
+
+        if let Some(bin) = opencode_binary_from_settings.as_deref() {
+            let trimmed = bin.trim();
             if !trimmed.is_empty() {
                 cmd = cmd.env("OPENCODE_BINARY", trimmed);
             }
@@ -1646,11 +3492,12 @@ async fn spawn_local_server(app: &tauri::AppHandle) -> Result<String> {
         let (rx, child) = match cmd.spawn() {
             Ok(v) => v,
             Err(err) => {
-                log::warn!("[sidecar] spawn failed on port {port}: {err}");
+                log::warn!(target: SIDECAR_LOG_TARGET, "event=spawn_failed port={port} error={err}");
                 continue;
             }
         };
 
+        let child_pid = child.pid();
         let app_handle = app.clone();
         tauri::async_runtime::spawn(async move {
             let mut rx = rx;
@@ -1658,23 +3505,38 @@ async fn spawn_local_server(app: &tauri::AppHandle) -> Result<String> {
                 match event {
                     CommandEvent::Stdout(bytes) => {
                         let line = String::from_utf8_lossy(&bytes);
+                        if let Some(state) = app_handle.try_state::<SidecarState>() {
+                            state.record_output(&line);
+                        }
                         if let Some(rest) = line.strip_prefix(SIDECAR_NOTIFY_PREFIX) {
-                            if let Ok(parsed) =
-                                serde_json::from_str::<SidecarNotifyPayload>(rest.trim())
-                            {
-                                maybe_show_sidecar_notification(&app_handle, parsed);
+                            match serde_json::from_str::<SidecarNotifyPayload>(rest.trim()) {
+                                Ok(parsed) => maybe_show_sidecar_notification(&app_handle, parsed),
+                                Err(err) => report_sidecar_notify_parse_error(&app_handle, rest, &err),
                             }
                         }
                     }
+                    CommandEvent::Stderr(bytes) => {
+                        if let Some(state) = app_handle.try_state::<SidecarState>() {
+                            state.record_output(&String::from_utf8_lossy(&bytes));
+                        }
+                    }
                     CommandEvent::Error(error) => {
-                        log::warn!("[sidecar] error: {error}");
+                        log::warn!(target: SIDECAR_LOG_TARGET, "event=error port={port} error={error}");
                     }
                     CommandEvent::Terminated(payload) => {
                         log::warn!(
-                            "[sidecar] terminated code={:?} signal={:?}",
+                            target: SIDECAR_LOG_TARGET,
+                            "event=terminated port={port} code={:?} signal={:?}",
                             payload.code,
                             payload.signal
                         );
+                        // Forget the handle if it's still this process, so status reports it as gone.
+                        if let Some(state) = app_handle.try_state::<SidecarState>() {
+                            let mut guard = state.child.lock().expect("sidecar mutex");
+                            if guard.as_ref().is_some_and(|current| current.pid() == child_pid) {
+                                *guard = None;
+                            }
+                        }
                         break;
                     }
                     _ => {}
@@ -1685,12 +3547,19 @@ async fn spawn_local_server(app: &tauri::AppHandle) -> Result<String> {
         if let Some(state) = app.try_state::<SidecarState>() {
             *state.child.lock().expect("sidecar mutex") = Some(child);
             *state.url.lock().expect("sidecar url mutex") = Some(url.clone());
+            *state.started_at.lock().expect("sidecar started mutex") = Some(SystemTime::now());
         }
 
-        if !wait_for_health(&url).await {
+        let healthy = wait_for_health(&url).await;
+        if let Some(state) = app.try_state::<SidecarState>() {
+            state.record_health(healthy);
+        }
+        if !healthy {
+            log::warn!(target: SIDECAR_LOG_TARGET, "event=health result=failed port={port}");
             kill_sidecar(app.clone());
             continue;
         }
+        log::info!(target: SIDECAR_LOG_TARGET, "event=health result=ok port={port}");
 
         let _ = write_desktop_local_port_to_disk(port);
         return Ok(url);
@@ -1699,9 +3568,11 @@ async fn spawn_local_server(app: &tauri::AppHandle) -> Result<String> {
     Err(anyhow!("Sidecar health check failed"))
 }
 
+const WEB_DIST_CANDIDATES: [&str; 2] = ["web-dist", "resources/web-dist"];
+
 fn resolve_web_dist_dir(app: &tauri::AppHandle) -> Result<PathBuf> {
-    let candidates = ["web-dist", "resources/web-dist"];
-    for candidate in candidates {
+    let mut attempted: Vec<String> = Vec::new();
+    for candidate in WEB_DIST_CANDIDATES {
         let path = app
             .path()
             .resolve(candidate, tauri::path::BaseDirectory::Resource)
@@ -1710,13 +3581,63 @@ fn resolve_web_dist_dir(app: &tauri::AppHandle) -> Result<PathBuf> {
         if fs::metadata(&index).is_ok() {
             return Ok(path);
         }
+        attempted.push(path.to_string_lossy().to_string());
     }
 
+    log::error!("[desktop] web assets missing; tried: {}", attempted.join(", "));
     Err(anyhow!(
         "Web assets missing in app resources (expected index.html under web-dist)"
     ))
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct WebDistCandidate {
+    path: Option<String>,
+    index_found: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DesktopAssetInfo {
+    dist_dir: Option<String>,
+    index_found: bool,
+    candidates: Vec<WebDistCandidate>,
+}
+
+/// Report where the bundled web UI was (or wasn't) found, for packaging diagnostics.
+#[tauri::command]
+fn desktop_get_asset_info(app: tauri::AppHandle) -> Result<DesktopAssetInfo, String> {
+    let candidates: Vec<WebDistCandidate> = WEB_DIST_CANDIDATES
+        .iter()
+        .map(|candidate| {
+            let path = app
+                .path()
+                .resolve(candidate, tauri::path::BaseDirectory::Resource)
+                .ok();
+            let index_found = path
+                .as_ref()
+                .map(|path| fs::metadata(path.join("index.html")).is_ok())
+                .unwrap_or(false);
+            WebDistCandidate {
+                path: path.map(|path| path.to_string_lossy().to_string()),
+                index_found,
+            }
+        })
+        .collect();
+
+    let dist_dir = candidates
+        .iter()
+        .find(|candidate| candidate.index_found)
+        .and_then(|candidate| candidate.path.clone());
+
+    Ok(DesktopAssetInfo {
+        index_found: dist_dir.is_some(),
+        dist_dir,
+        candidates,
+    })
+}
+
 fn normalize_server_url(input: &str) -> Option<String> {
     let trimmed = input.trim();
     if trimmed.is_empty() {
@@ -1735,6 +3656,13 @@ fn normalize_server_url(input: &str) -> Option<String> {
     }
 }
 
+/// Server URL forced for the whole session via `OPENCHAMBER_SERVER_URL`.
+fn server_url_override() -> Option<String> {
+    env::var("OPENCHAMBER_SERVER_URL")
+        .ok()
+        .and_then(|raw| normalize_server_url(&raw))
+}
+
 #[derive(Deserialize)]
 struct DesktopNotifyPayload {
     title: Option<String>,
@@ -1753,31 +3681,275 @@ fn desktop_notify(
         tag: None,
     });
 
+    if let Some(tag) = payload.tag {
+        if is_nonempty_string(&tag) {
+            let _ = tag;
+        }
+    }
+
+    show_desktop_notification(
+        &app,
+        payload.title.unwrap_or_else(|| "OpenChamber".to_string()),
+        payload.body,
+    )
+    .map(|_| true)
+}
+
+/// Show a notification with the configured sound and platform hint.
+///
+/// The notification plugin has no urgency or toast scenario, so when
+/// `desktopNotificationUrgency` sets one the notification is shown directly:
+/// through notify-rust on Linux, as a WinRT toast on Windows.
+fn show_desktop_notification(
+    app: &tauri::AppHandle,
+    title: String,
+    body: Option<String>,
+) -> Result<(), String> {
+    #[cfg(target_os = "linux")]
+    if let Some(urgency) = read_notification_urgency_from_disk() {
+        let mut notification = notify_rust::Notification::new();
+        notification.summary(&title).urgency(urgency).auto_icon();
+        if let Some(body) = body.as_deref().filter(|b| is_nonempty_string(b)) {
+            notification.body(body);
+        }
+        if let Some(sound) = read_notification_sound_from_disk() {
+            notification.sound_name(&sound);
+        }
+        tauri::async_runtime::spawn_blocking(move || {
+            if let Err(err) = notification.show() {
+                log::warn!("[desktop] failed to show notification: {err}");
+            }
+        });
+        return Ok(());
+    }
+
+    #[cfg(target_os = "windows")]
+    if let Some(scenario) = read_toast_scenario_from_disk() {
+        let app_id = windows_toast_app_id(app)?;
+        let body = body.filter(|b| is_nonempty_string(b)).unwrap_or_default();
+        let sound = read_notification_sound_from_disk();
+        // Built on the worker: the toast holds COM handles that aren't `Send`.
+        tauri::async_runtime::spawn_blocking(move || {
+            use std::str::FromStr;
+            use tauri_winrt_notification::{Sound, Toast};
+
+            // Same sound mapping as the plugin: unknown or missing names are silent.
+            let sound = sound.and_then(|name| Sound::from_str(&name).ok());
+            let result = Toast::new(&app_id)
+                .title(&title)
+                .text1(&body)
+                .sound(sound)
+                .scenario(scenario)
+                .show();
+            if let Err(err) = result {
+                log::warn!("[desktop] failed to show notification: {err:?}");
+            }
+        });
+        return Ok(());
+    }
+
+    desktop_notification_builder(app, title, body)
+        .show()
+        .map_err(|err| err.to_string())
+}
+
+/// Notification builder used when no platform hint is configured.
+fn desktop_notification_builder(
+    app: &tauri::AppHandle,
+    title: String,
+    body: Option<String>,
+) -> tauri_plugin_notification::NotificationBuilder<tauri::Wry> {
     use tauri_plugin_notification::NotificationExt;
 
-    let mut builder = app
-        .notification()
-        .builder()
-        .title(payload.title.unwrap_or_else(|| "OpenChamber".to_string()));
+    let mut builder = app.notification().builder().title(title);
 
-    if let Some(body) = payload.body {
+    if let Some(body) = body {
         if is_nonempty_string(&body) {
             builder = builder.body(body);
         }
     }
 
-    if let Some(tag) = payload.tag {
-        if is_nonempty_string(&tag) {
-            let _ = tag;
+    if let Some(sound) = read_notification_sound_from_disk() {
+        builder = builder.sound(sound);
+    }
+
+    builder
+}
+
+/// Sound for the current platform from `desktopNotificationSound`
+/// (`{ "macos": "Glass", "windows": "Reminder", "linux": "message-new-instant" }`).
+/// A missing platform keeps the default (Glass on macOS, none elsewhere); `null` or
+/// an empty string silences it. Names are passed through as-is: macOS system sound,
+/// Windows toast audio, or freedesktop sound-name hint on Linux.
+fn read_notification_sound_from_disk() -> Option<String> {
+    let default = if cfg!(target_os = "macos") { Some("Glass".to_string()) } else { None };
+    let platform = if cfg!(target_os = "macos") {
+        "macos"
+    } else if cfg!(target_os = "windows") {
+        "windows"
+    } else {
+        "linux"
+    };
+
+    match read_settings_value("desktopNotificationSound").as_ref().and_then(|v| v.get(platform)) {
+        None => default,
+        Some(serde_json::Value::String(sound)) if is_nonempty_string(sound) => Some(sound.trim().to_string()),
+        Some(serde_json::Value::String(_)) | Some(serde_json::Value::Null) => None,
+        Some(_) => default,
+    }
+}
+
+/// Platform hint for the current platform from `desktopNotificationUrgency`
+/// (`{ "linux": "critical", "windows": "reminder" }`). Unset, `null` or empty keeps
+/// the plugin's default behavior; macOS has no equivalent and ignores the key.
+#[cfg(any(target_os = "linux", target_os = "windows"))]
+fn read_notification_hint_from_disk() -> Option<String> {
+    let platform = if cfg!(target_os = "windows") { "windows" } else { "linux" };
+
+    read_settings_value("desktopNotificationUrgency")
+        .as_ref()
+        .and_then(|v| v.get(platform))
+        .and_then(|v| v.as_str())
+        .filter(|hint| is_nonempty_string(hint))
+        .map(|hint| hint.trim().to_string())
+}
+
+/// Linux urgency hint: `low`, `normal` or `critical`.
+#[cfg(target_os = "linux")]
+fn read_notification_urgency_from_disk() -> Option<notify_rust::Urgency> {
+    let hint = read_notification_hint_from_disk()?;
+    match notify_rust::Urgency::try_from(hint.as_str()) {
+        Ok(urgency) => Some(urgency),
+        Err(_) => {
+            log::warn!("[desktop] ignoring unknown notification urgency: {hint}");
+            None
         }
     }
+}
 
-    #[cfg(target_os = "macos")]
-    {
-        builder = builder.sound("Glass");
+/// Windows toast scenario: `default`, `alarm`, `reminder` or `incomingCall`.
+#[cfg(target_os = "windows")]
+fn read_toast_scenario_from_disk() -> Option<tauri_winrt_notification::Scenario> {
+    use tauri_winrt_notification::Scenario;
+
+    let hint = read_notification_hint_from_disk()?;
+    match hint.as_str() {
+        "default" => Some(Scenario::Default),
+        "alarm" => Some(Scenario::Alarm),
+        "reminder" => Some(Scenario::Reminder),
+        "incomingCall" => Some(Scenario::IncomingCall),
+        _ => {
+            log::warn!("[desktop] ignoring unknown notification scenario: {hint}");
+            None
+        }
+    }
+}
+
+/// AppUserModelID for direct toasts, matching the notification plugin: the bundle
+/// identifier for the installed app, PowerShell's when running from `target/`.
+#[cfg(target_os = "windows")]
+fn windows_toast_app_id(app: &tauri::AppHandle) -> Result<String, String> {
+    use std::path::MAIN_SEPARATOR as SEP;
+
+    let exe = tauri::utils::platform::current_exe().map_err(|err| err.to_string())?;
+    let exe_dir = exe
+        .parent()
+        .map(|dir| dir.display().to_string())
+        .unwrap_or_default();
+    let dev_build = exe_dir.ends_with(&format!("{SEP}target{SEP}debug"))
+        || exe_dir.ends_with(&format!("{SEP}target{SEP}release"));
+
+    Ok(if dev_build {
+        tauri_winrt_notification::Toast::POWERSHELL_APP_ID.to_string()
+    } else {
+        app.config().identifier.clone()
+    })
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TestNotificationResult {
+    shown: bool,
+    error: Option<String>,
+    /// Authorization state as reported by the notification plugin.
+    permission: Option<String>,
+}
+
+/// Show a fixed test notification, bypassing focus-based suppression, so users can
+/// tell an OS/permission problem apart from `require_hidden` suppression.
+#[tauri::command]
+fn desktop_test_notification(app: tauri::AppHandle) -> Result<TestNotificationResult, String> {
+    use tauri_plugin_notification::NotificationExt;
+
+    let permission = app
+        .notification()
+        .permission_state()
+        .map(|state| state.to_string())
+        .ok();
+
+    let result = show_desktop_notification(
+        &app,
+        "OpenChamber".to_string(),
+        Some("Test notification: notifications are working.".to_string()),
+    );
+
+    Ok(TestNotificationResult {
+        shown: result.is_ok(),
+        error: result.err(),
+        permission,
+    })
+}
+
+/// Notification authorization state: `granted`, `denied` or `prompt`.
+///
+/// The desktop notification plugin doesn't query the OS and reports `granted`
+/// everywhere, so on desktop this only rules out an explicit denial it knows about.
+#[tauri::command]
+fn desktop_notification_permission_status(app: tauri::AppHandle) -> Result<String, String> {
+    use tauri_plugin_notification::NotificationExt;
+
+    app.notification()
+        .permission_state()
+        .map(|state| state.to_string())
+        .map_err(|err| err.to_string())
+}
+
+/// Ask the OS for notification permission and return the resulting state.
+#[tauri::command]
+fn desktop_request_notification_permission(app: tauri::AppHandle) -> Result<String, String> {
+    use tauri_plugin_notification::NotificationExt;
+
+    app.notification()
+        .request_permission()
+        .map(|state| state.to_string())
+        .map_err(|err| err.to_string())
+}
+
+/// Local-only mode (`OPENCHAMBER_LOCAL_ONLY` or `desktopLocalOnly`): windows always
+/// target the bundled sidecar, ignoring `desktopHosts`, the default host and
+/// `OPENCHAMBER_SERVER_URL`. The hosts config can still be edited.
+fn is_local_only() -> bool {
+    if let Ok(raw) = env::var("OPENCHAMBER_LOCAL_ONLY") {
+        let raw = raw.trim();
+        if !raw.is_empty() {
+            return raw != "0" && !raw.eq_ignore_ascii_case("false");
+        }
     }
 
-    builder.show().map(|_| true).map_err(|err| err.to_string())
+    read_settings_bool("desktopLocalOnly", false)
+}
+
+/// Whether the built-in updater is turned off for managed deployments, via
+/// `OPENCHAMBER_DISABLE_UPDATER` or `desktopDisableUpdater` in settings.
+fn is_updater_disabled() -> bool {
+    if let Ok(raw) = env::var("OPENCHAMBER_DISABLE_UPDATER") {
+        let raw = raw.trim();
+        if !raw.is_empty() {
+            return raw != "0" && !raw.eq_ignore_ascii_case("false");
+        }
+    }
+
+    read_settings_bool("desktopDisableUpdater", false)
 }
 
 #[tauri::command]
@@ -1785,26 +3957,30 @@ async fn desktop_check_for_updates(
     app: tauri::AppHandle,
     pending: tauri::State<'_, PendingUpdate>,
 ) -> Result<DesktopUpdateInfo, String> {
+    if is_updater_disabled() {
+        return Ok(DesktopUpdateInfo {
+            available: false,
+            current_version: app.package_info().version.to_string(),
+            version: None,
+            body: None,
+            date: None,
+        });
+    }
+
     let updater = app.updater().map_err(|err| err.to_string())?;
     let update = updater.check().await.map_err(|err| err.to_string())?;
 
     let current_version = app.package_info().version.to_string();
 
-    let info = if let Some(update) = update {
-        *pending.0.lock().expect("pending update mutex") = Some(update.clone());
-        let mut body = update.body.clone();
-        if is_placeholder_release_notes(&body) {
+    let info = if let Some(mut update) = update {
+        if is_placeholder_release_notes(&update.body) {
             if let Some(notes) = fetch_changelog_notes(&current_version, &update.version).await {
-                body = Some(notes);
+                update.body = Some(notes);
             }
         }
-        DesktopUpdateInfo {
-            available: true,
-            current_version,
-            version: Some(update.version.clone()),
-            body,
-            date: update.date.map(|date| date.to_string()),
-        }
+        // Stage with the resolved notes so desktop_pending_update_info can return them.
+        *pending.0.lock().expect("pending update mutex") = Some(update.clone());
+        pending_update_info(current_version, &update)
     } else {
         *pending.0.lock().expect("pending update mutex") = None;
         DesktopUpdateInfo {
@@ -1819,15 +3995,72 @@ async fn desktop_check_for_updates(
     Ok(info)
 }
 
+fn pending_update_info(current_version: String, update: &tauri_plugin_updater::Update) -> DesktopUpdateInfo {
+    DesktopUpdateInfo {
+        available: true,
+        current_version,
+        version: Some(update.version.clone()),
+        body: update.body.clone(),
+        date: update.date.map(|date| date.to_string()),
+    }
+}
+
+/// The update staged by the last `desktop_check_for_updates`, without a network check.
+#[tauri::command]
+fn desktop_pending_update_info(app: tauri::AppHandle, pending: tauri::State<'_, PendingUpdate>) -> DesktopUpdateInfo {
+    let current_version = app.package_info().version.to_string();
+    match pending.0.lock().expect("pending update mutex").as_ref() {
+        Some(update) => pending_update_info(current_version, update),
+        None => DesktopUpdateInfo {
+            available: false,
+            current_version,
+            version: None,
+            body: None,
+            date: None,
+        },
+    }
+}
+
 #[tauri::command]
 async fn desktop_download_and_install_update(
     app: tauri::AppHandle,
     pending: tauri::State<'_, PendingUpdate>,
+    install_id: Option<String>,
+) -> Result<(), String> {
+    if is_updater_disabled() {
+        return Err("UpdaterDisabled".to_string());
+    }
+    if UPDATE_INSTALL_IN_FLIGHT.swap(true, Ordering::SeqCst) {
+        return Err("InstallInProgress".to_string());
+    }
+
+    let install_id = install_id
+        .filter(|id| is_nonempty_string(id))
+        .unwrap_or_else(|| format!("install-{}", UPDATE_INSTALL_COUNTER.fetch_add(1, Ordering::Relaxed)));
+    let result = download_and_install_pending_update(&app, &pending, &install_id).await;
+    UPDATE_INSTALL_IN_FLIGHT.store(false, Ordering::SeqCst);
+    result
+}
+
+async fn download_and_install_pending_update(
+    app: &tauri::AppHandle,
+    pending: &PendingUpdate,
+    install_id: &str,
 ) -> Result<(), String> {
     let Some(update) = pending.0.lock().expect("pending update mutex").take() else {
         return Err("No pending update".to_string());
     };
 
+    let emit_progress = |event: UpdateProgressEvent| {
+        let _ = app.emit(
+            "openchamber:update-progress",
+            UpdateProgressPayload {
+                install_id: install_id.to_string(),
+                event,
+            },
+        );
+    };
+
     let mut downloaded: u64 = 0;
     let mut total: Option<u64> = None;
     let mut started = false;
@@ -1837,25 +4070,19 @@ async fn desktop_download_and_install_update(
             |chunk_length, content_length| {
                 if !started {
                     total = content_length;
-                    let _ = app.emit(
-                        "openchamber:update-progress",
-                        UpdateProgressEvent::Started { content_length },
-                    );
+                    emit_progress(UpdateProgressEvent::Started { content_length });
                     started = true;
                 }
 
                 downloaded = downloaded.saturating_add(chunk_length as u64);
-                let _ = app.emit(
-                    "openchamber:update-progress",
-                    UpdateProgressEvent::Progress {
-                        chunk_length,
-                        downloaded,
-                        total,
-                    },
-                );
+                emit_progress(UpdateProgressEvent::Progress {
+                    chunk_length,
+                    downloaded,
+                    total,
+                });
             },
             || {
-                let _ = app.emit("openchamber:update-progress", UpdateProgressEvent::Finished);
+                emit_progress(UpdateProgressEvent::Finished);
             },
         )
         .await
@@ -1869,6 +4096,22 @@ fn desktop_restart(app: tauri::AppHandle) {
     app.restart();
 }
 
+/// Quit the app, making sure the sidecar is killed first so no orphaned
+/// `openchamber-server` is left behind. Safe to call repeatedly during shutdown.
+#[tauri::command]
+fn desktop_quit(app: tauri::AppHandle) {
+    if APP_EXITING.load(Ordering::SeqCst) {
+        return;
+    }
+    persist_window_session(&app);
+    if APP_EXITING.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    kill_sidecar(app.clone());
+    app.exit(0);
+}
+
 /// Create a new desktop window from the UI layer.
 ///
 /// IMPORTANT: This command MUST remain synchronous (not `async`). Tauri runs
@@ -1898,26 +4141,277 @@ fn desktop_new_window_at_url(app: tauri::AppHandle, url: String) -> Result<(), S
         .and_then(|state| state.local_origin.lock().expect("desktop local origin mutex").clone())
         .ok_or_else(|| "Local origin not yet known (sidecar may still be starting)".to_string())?;
 
-    create_window(&app, &url, &local_origin, false).map_err(|e| e.to_string())
+    create_window(&app, &url, &local_origin, None).map_err(|e| e.to_string())
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DesktopDiagnostics {
+    app: DesktopAppInfo,
+    update_endpoints: Vec<String>,
+    local_url: Option<String>,
+    local_port: Option<u16>,
+    local_origin: Option<String>,
+    sidecar_running: bool,
+    sidecar_path: Option<String>,
+    hosts: DiagnosticsHostsConfig,
+    unreachable_hosts: Vec<String>,
+    window_count: usize,
+    recent_sidecar_output: Vec<String>,
+}
+
+/// `DesktopHostsConfig` for bug reports: per-host init scripts are arbitrary user JS
+/// that may hold tokens, so only their length is reported.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DiagnosticsHostsConfig {
+    hosts: Vec<DiagnosticsHost>,
+    default_host_id: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DiagnosticsHost {
+    id: String,
+    label: String,
+    url: String,
+    /// Length in bytes of the host's init script, if one is set.
+    init_script_len: Option<usize>,
+}
+
+fn diagnostics_hosts_config(config: DesktopHostsConfig) -> DiagnosticsHostsConfig {
+    DiagnosticsHostsConfig {
+        hosts: config
+            .hosts
+            .into_iter()
+            .map(|host| DiagnosticsHost {
+                id: host.id,
+                label: host.label,
+                url: host.url,
+                init_script_len: host.init_script.map(|script| script.len()),
+            })
+            .collect(),
+        default_host_id: config.default_host_id,
+    }
+}
+
+/// Mask values that look like credentials (`token=...`, `Bearer ...`, etc.).
+fn redact_secrets(line: &str) -> String {
+    const SECRET_MARKERS: [&str; 6] = ["token=", "key=", "secret=", "password=", "auth=", "bearer "];
+
+    let lower = line.to_ascii_lowercase();
+    let mut redacted = String::with_capacity(line.len());
+    let mut cursor = 0;
+    while cursor < line.len() {
+        let next = SECRET_MARKERS
+            .iter()
+            .filter_map(|marker| lower[cursor..].find(marker).map(|idx| (cursor + idx, marker.len())))
+            .min_by_key(|(idx, _)| *idx);
+        let Some((idx, marker_len)) = next else {
+            redacted.push_str(&line[cursor..]);
+            break;
+        };
+        let value_start = idx + marker_len;
+        let value_end = line[value_start..]
+            .find(|ch: char| ch.is_whitespace() || matches!(ch, '&' | '"' | '\'' | ',' | ';'))
+            .map(|offset| value_start + offset)
+            .unwrap_or(line.len());
+        redacted.push_str(&line[cursor..value_start]);
+        if value_end > value_start {
+            redacted.push_str("[redacted]");
+        }
+        cursor = value_end;
+    }
+    redacted
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DesktopAppInfo {
+    name: String,
+    version: String,
+    build_profile: String,
+    platform: String,
+    arch: String,
+    macos_major: Option<u32>,
+    /// `stable`, or the prerelease tag of the version (e.g. `beta` for `1.7.0-beta.2`).
+    update_channel: String,
+    updater_disabled: bool,
+}
+
+/// App name/version and build metadata, the single source for About and bug reports.
+fn app_info(app: &tauri::AppHandle) -> DesktopAppInfo {
+    let pkg_info = app.package_info();
+    let update_channel = pkg_info
+        .version
+        .pre
+        .as_str()
+        .split('.')
+        .next()
+        .filter(|tag| !tag.is_empty())
+        .unwrap_or("stable")
+        .to_string();
+
+    DesktopAppInfo {
+        name: pkg_info.name.clone(),
+        version: pkg_info.version.to_string(),
+        build_profile: if cfg!(debug_assertions) { "debug" } else { "release" }.to_string(),
+        platform: env::consts::OS.to_string(),
+        arch: env::consts::ARCH.to_string(),
+        macos_major: macos_major_version(),
+        update_channel,
+        updater_disabled: is_updater_disabled(),
+    }
+}
+
+#[tauri::command]
+fn desktop_get_app_info(app: tauri::AppHandle) -> Result<DesktopAppInfo, String> {
+    Ok(app_info(&app))
+}
+
+/// Collect desktop-side diagnostic state into one JSON-serializable blob for bug reports.
+#[tauri::command]
+fn desktop_collect_diagnostics(app: tauri::AppHandle) -> Result<DesktopDiagnostics, String> {
+    let update_endpoints = app
+        .config()
+        .plugins
+        .0
+        .get("updater")
+        .and_then(|updater| updater.get("endpoints"))
+        .and_then(|endpoints| endpoints.as_array())
+        .map(|endpoints| {
+            endpoints
+                .iter()
+                .filter_map(|value| value.as_str().map(|s| s.to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let (local_url, sidecar_running, sidecar_path, recent_sidecar_output) = match app.try_state::<SidecarState>() {
+        Some(state) => (
+            state.url.lock().expect("sidecar url mutex").clone(),
+            state.child.lock().expect("sidecar mutex").is_some(),
+            state.path.lock().expect("sidecar path mutex").clone(),
+            state
+                .recent_output
+                .lock()
+                .expect("sidecar output mutex")
+                .iter()
+                .map(|line| redact_secrets(line))
+                .collect(),
+        ),
+        None => (None, false, None, Vec::new()),
+    };
+    let local_port = local_url
+        .as_deref()
+        .and_then(|url| url::Url::parse(url).ok())
+        .and_then(|url| url.port_or_known_default());
+
+    let (local_origin, unreachable_hosts) = match app.try_state::<DesktopUiInjectionState>() {
+        Some(state) => {
+            let mut hosts: Vec<String> = state
+                .unreachable_hosts
+                .lock()
+                .expect("unreachable hosts mutex")
+                .iter()
+                .cloned()
+                .collect();
+            hosts.sort();
+            (state.local_origin.lock().expect("desktop local origin mutex").clone(), hosts)
+        }
+        None => (None, Vec::new()),
+    };
+
+    Ok(DesktopDiagnostics {
+        app: app_info(&app),
+        update_endpoints,
+        local_url,
+        local_port,
+        local_origin,
+        sidecar_running,
+        sidecar_path,
+        hosts: diagnostics_hosts_config(read_desktop_hosts_config_from_disk()),
+        unreachable_hosts,
+        window_count: app.webview_windows().len(),
+        recent_sidecar_output,
+    })
+}
+
+/// Open a new window at an in-app route on the given host (default host when omitted).
+///
+/// IMPORTANT: Must remain synchronous -- see `desktop_new_window` doc comment.
+#[tauri::command]
+fn desktop_new_window_at_route(app: tauri::AppHandle, route: String, host_id: Option<String>) -> Result<(), String> {
+    let (local_origin, local_ui_url) = resolve_local_ui_url(&app)
+        .ok_or_else(|| "Local origin not yet known (sidecar may still be starting)".to_string())?;
+
+    let host_id = host_id.filter(|id| is_nonempty_string(id));
+    let base_url = resolve_window_host_url(&app, host_id.as_deref(), &local_ui_url);
+    let url = build_route_url(&base_url, &route)?;
+
+    create_window(&app, &url, &local_origin, None).map_err(|e| e.to_string())
+}
+
+/// Largest base64 payload `desktop_read_file` returns for a single file.
+const MAX_READ_FILE_PAYLOAD_BYTES: u64 = 50 * 1024 * 1024;
+/// Cap on base64 payload bytes being read and encoded by concurrent `desktop_read_file`
+/// calls. It bounds what the shell holds at once; the UI's copies are its own concern.
+const MAX_READ_FILE_BYTES_IN_FLIGHT: u64 = 200 * 1024 * 1024;
+static READ_FILE_BYTES_IN_FLIGHT: AtomicU64 = AtomicU64::new(0);
+
+/// Reservation against `READ_FILE_BYTES_IN_FLIGHT`, released on drop.
+struct ReadFileReservation(u64);
+
+impl ReadFileReservation {
+    fn acquire(bytes: u64) -> Option<Self> {
+        READ_FILE_BYTES_IN_FLIGHT
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |current| {
+                current
+                    .checked_add(bytes)
+                    .filter(|total| *total <= MAX_READ_FILE_BYTES_IN_FLIGHT)
+            })
+            .ok()
+            .map(|_| Self(bytes))
+    }
+}
+
+impl Drop for ReadFileReservation {
+    fn drop(&mut self) {
+        READ_FILE_BYTES_IN_FLIGHT.fetch_sub(self.0, Ordering::SeqCst);
+    }
+}
+
+/// Size of the standard (padded) base64 encoding of `len` bytes.
+fn base64_encoded_len(len: u64) -> u64 {
+    len.div_ceil(3).saturating_mul(4)
 }
 
 /// Read a file and return its content as base64 with mime type detection.
-/// Used for drag-drop file attachments in desktop app.
+/// Used for drag-drop file attachments in desktop app. Async so several drops are read
+/// off the main thread in parallel, within `MAX_READ_FILE_BYTES_IN_FLIGHT`.
 #[tauri::command]
-fn desktop_read_file(path: String) -> Result<FileContent, String> {
-    use std::path::Path;
+async fn desktop_read_file(path: String) -> Result<FileContent, String> {
+    tauri::async_runtime::spawn_blocking(move || read_file_content(Path::new(&path)))
+        .await
+        .map_err(|err| err.to_string())?
+}
 
-    let path = Path::new(&path);
+fn read_file_content(path: &Path) -> Result<FileContent, String> {
 
-    // Check file size (max 50MB)
+    // The limit applies to the base64 payload the UI receives, not the raw size.
     let metadata = std::fs::metadata(path).map_err(|e| format!("Failed to read file metadata: {e}"))?;
-    let size = metadata.len();
-    if size > 50 * 1024 * 1024 {
-        return Err("File is too large. Maximum size is 50MB.".to_string());
+    let payload_size = base64_encoded_len(metadata.len());
+    if payload_size > MAX_READ_FILE_PAYLOAD_BYTES {
+        return Err("File is too large. Maximum encoded size is 50MB.".to_string());
     }
+    let _reservation = ReadFileReservation::acquire(payload_size)
+        .ok_or_else(|| "Too many files are being read at once. Try again shortly.".to_string())?;
 
     // Read file bytes
     let bytes = std::fs::read(path).map_err(|e| format!("Failed to read file: {e}"))?;
+    if base64_encoded_len(bytes.len() as u64) > payload_size {
+        return Err("File changed while it was being read.".to_string());
+    }
 
     // Detect mime type from extension
     let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("").to_lowercase();
@@ -1960,6 +4454,85 @@ struct FileContent {
     size: usize,
 }
 
+/// Read a file as text, honouring a UTF-8/UTF-16 BOM (UTF-8 when absent).
+/// Reads at most `max_bytes` (default and cap 50MB); a character split by the
+/// cut is dropped and `truncated` is set. Invalid data is an error, never lossy.
+#[tauri::command]
+fn desktop_read_text_file(path: String, max_bytes: Option<u64>) -> Result<TextFileContent, String> {
+    use std::io::Read;
+
+    const MAX_TEXT_BYTES: u64 = 50 * 1024 * 1024;
+    let limit = max_bytes.unwrap_or(MAX_TEXT_BYTES).min(MAX_TEXT_BYTES);
+
+    let file = fs::File::open(&path).map_err(|e| format!("Failed to open file: {e}"))?;
+    let size = file
+        .metadata()
+        .map_err(|e| format!("Failed to read file metadata: {e}"))?
+        .len();
+
+    let mut bytes = Vec::new();
+    file.take(limit)
+        .read_to_end(&mut bytes)
+        .map_err(|e| format!("Failed to read file: {e}"))?;
+    let truncated = size > bytes.len() as u64;
+
+    let (encoding, text) = if let Some(body) = bytes.strip_prefix(&[0xEF, 0xBB, 0xBF]) {
+        ("utf-8", decode_utf8_text(body, truncated)?)
+    } else if let Some(body) = bytes.strip_prefix(&[0xFF, 0xFE]) {
+        ("utf-16le", decode_utf16_text(body, false, truncated)?)
+    } else if let Some(body) = bytes.strip_prefix(&[0xFE, 0xFF]) {
+        ("utf-16be", decode_utf16_text(body, true, truncated)?)
+    } else {
+        ("utf-8", decode_utf8_text(&bytes, truncated)?)
+    };
+
+    Ok(TextFileContent {
+        text,
+        encoding: encoding.to_string(),
+        truncated,
+        size,
+    })
+}
+
+fn decode_utf8_text(bytes: &[u8], truncated: bool) -> Result<String, String> {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => Ok(text.to_string()),
+        // An incomplete sequence at the very end is just the read limit cutting a character.
+        Err(err) if truncated && err.error_len().is_none() => {
+            Ok(String::from_utf8_lossy(&bytes[..err.valid_up_to()]).into_owned())
+        }
+        Err(err) => Err(format!("File is not valid UTF-8 (invalid byte at offset {})", err.valid_up_to())),
+    }
+}
+
+fn decode_utf16_text(bytes: &[u8], big_endian: bool, truncated: bool) -> Result<String, String> {
+    if !bytes.len().is_multiple_of(2) && !truncated {
+        return Err("File is not valid UTF-16 (odd byte length)".to_string());
+    }
+    let mut units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|pair| {
+            if big_endian {
+                u16::from_be_bytes([pair[0], pair[1]])
+            } else {
+                u16::from_le_bytes([pair[0], pair[1]])
+            }
+        })
+        .collect();
+    if truncated && units.last().is_some_and(|unit| (0xD800..0xDC00).contains(unit)) {
+        units.pop();
+    }
+    String::from_utf16(&units).map_err(|_| "File is not valid UTF-16 (unpaired surrogate)".to_string())
+}
+
+#[derive(Serialize)]
+struct TextFileContent {
+    text: String,
+    encoding: String,
+    truncated: bool,
+    size: u64,
+}
+
 #[cfg(target_os = "macos")]
 fn macos_major_version() -> Option<u32> {
     fn cmd_stdout(cmd: &str, args: &[&str]) -> Option<String> {
@@ -2003,22 +4576,28 @@ fn macos_major_version() -> Option<u32> {
 
 /// Build the initialization script injected into every webview window.
 /// This is computed once and reused for all windows.
-fn build_init_script(local_origin: &str) -> String {
+fn build_init_script(app: &tauri::AppHandle, local_origin: &str) -> String {
     let home = std::env::var(if cfg!(windows) { "USERPROFILE" } else { "HOME" }).unwrap_or_default();
     let macos_major = macos_major_version().unwrap_or(0);
+    let version = app.package_info().version.to_string();
 
     let home_json = serde_json::to_string(&home).unwrap_or_else(|_| "\"\"".into());
     let local_json = serde_json::to_string(local_origin).unwrap_or_else(|_| "\"\"".into());
+    let version_json = serde_json::to_string(&version).unwrap_or_else(|_| "\"\"".into());
+    let platform_json = serde_json::to_string(env::consts::OS).unwrap_or_else(|_| "\"\"".into());
+    let updater_disabled = is_updater_disabled();
+    let local_only = is_local_only();
 
+    // `__OPENCHAMBER_DESKTOP__` is the authoritative "running inside the Tauri shell" flag.
     let mut init_script = format!(
-        "(function(){{try{{window.__OPENCHAMBER_HOME__={home_json};window.__OPENCHAMBER_MACOS_MAJOR__={macos_major};window.__OPENCHAMBER_LOCAL_ORIGIN__={local_json};}}catch(_e){{}}}})();"
+        "(function(){{try{{window.__OPENCHAMBER_DESKTOP__=true;window.__OPENCHAMBER_DESKTOP_VERSION__={version_json};window.__OPENCHAMBER_DESKTOP_PLATFORM__={platform_json};window.__OPENCHAMBER_UPDATER_DISABLED__={updater_disabled};window.__OPENCHAMBER_LOCAL_ONLY__={local_only};window.__OPENCHAMBER_HOME__={home_json};window.__OPENCHAMBER_MACOS_MAJOR__={macos_major};window.__OPENCHAMBER_LOCAL_ORIGIN__={local_json};}}catch(_e){{}}}})();"
     );
 
     // Cleanup: older builds injected a native-ish Instance switcher button into pages.
     // Remove it if present so the UI-owned host switcher is the only one.
     init_script.push_str("\ntry{var old=document.getElementById('__oc-instance-switcher');if(old)old.remove();}catch(_e){}");
 
-    if !cfg!(debug_assertions) {
+    if !cfg!(debug_assertions) && !read_desktop_native_context_menu_from_disk() {
         init_script.push_str("\ntry{document.addEventListener('contextmenu',function(e){var t=e&&e.target;if(!t||typeof t.closest!=='function'){e.preventDefault();return;}if(t.closest('.terminal-viewport-container,[data-oc-allow-native-contextmenu],input,textarea,[contenteditable=\"true\"]')){return;}e.preventDefault();},true);}catch(_e){}");
     }
 
@@ -2048,95 +4627,459 @@ fn is_window_state_visible(app: &tauri::AppHandle, state: &DesktopWindowState) -
             continue;
         }
 
-        let position = monitor.position();
-        let size = monitor.size();
-
-        let monitor_left = position.x as f64 / scale;
-        let monitor_top = position.y as f64 / scale;
-        let monitor_right = monitor_left + size.width as f64 / scale;
-        let monitor_bottom = monitor_top + size.height as f64 / scale;
+        let position = monitor.position();
+        let size = monitor.size();
+
+        let monitor_left = position.x as f64 / scale;
+        let monitor_top = position.y as f64 / scale;
+        let monitor_right = monitor_left + size.width as f64 / scale;
+        let monitor_bottom = monitor_top + size.height as f64 / scale;
+
+        let overlap_width = right.min(monitor_right) - left.max(monitor_left);
+        let overlap_height = bottom.min(monitor_bottom) - top.max(monitor_top);
+        if overlap_width > 0.0 && overlap_height > 0.0 {
+            return true;
+        }
+    }
+
+    false
+}
+
+fn capture_window_state(window: &tauri::Window) -> Option<DesktopWindowState> {
+    let position = window.outer_position().ok()?;
+    let size = window.inner_size().ok()?;
+    let scale = window
+        .scale_factor()
+        .ok()
+        .filter(|value| value.is_finite() && *value > 0.0)
+        .unwrap_or(1.0);
+
+    Some(DesktopWindowState {
+        x: (position.x as f64 / scale).round() as i32,
+        y: (position.y as f64 / scale).round() as i32,
+        width: (size.width as f64 / scale).round().max(MIN_WINDOW_WIDTH as f64) as u32,
+        height: (size.height as f64 / scale).round().max(MIN_WINDOW_HEIGHT as f64) as u32,
+        maximized: window.is_maximized().unwrap_or(false),
+        fullscreen: window.is_fullscreen().unwrap_or(false),
+    })
+}
+
+fn schedule_window_state_persist(window: tauri::Window, immediate: bool) {
+    let app = window.app_handle().clone();
+    let label = window.label().to_string();
+    let revision = {
+        let Some(state) = app.try_state::<WindowGeometryDebounceState>() else {
+            return;
+        };
+        let mut guard = state.revisions.lock().expect("window geometry debounce mutex");
+        let next = guard.get(&label).copied().unwrap_or(0).saturating_add(1);
+        guard.insert(label.clone(), next);
+        next
+    };
+
+    tauri::async_runtime::spawn(async move {
+        if !immediate {
+            tokio::time::sleep(Duration::from_millis(WINDOW_STATE_DEBOUNCE_MS)).await;
+        }
+
+        let is_latest = app
+            .try_state::<WindowGeometryDebounceState>()
+            .map(|state| {
+                state
+                    .revisions
+                    .lock()
+                    .map(|guard| guard.get(&label).copied() == Some(revision))
+                    .unwrap_or(false)
+            })
+            .unwrap_or(false);
+        if !is_latest {
+            return;
+        }
+
+        // Capture everything first, then write session and geometry in one update so
+        // each debounce touches settings.json (and emits settings-changed) once.
+        // A close leaves the session to the Destroyed handler, which runs once the
+        // window is really gone.
+        let session = if immediate || APP_EXITING.load(Ordering::SeqCst) {
+            Vec::new()
+        } else {
+            capture_window_session(&app)
+        };
+        let session_labels = open_window_labels(&app);
+        let snapshot = capture_window_state(&window);
+        // A shared identity (same host and project in several windows) would make the
+        // windows overwrite each other's entry; those rely on their session state.
+        let identity = window_identity(&app, &label).filter(|identity| {
+            open_window_labels(&app)
+                .iter()
+                .all(|other| other == &label || window_identity(&app, other).as_ref() != Some(identity))
+        });
+        if session.is_empty() && snapshot.is_none() {
+            return;
+        }
+
+        let result = update_settings_file(|root| {
+            // Checked under the settings lock: if a window opened or closed since the
+            // capture, its own handler writes (or already wrote) the fresher session.
+            if !session.is_empty() && open_window_labels(&app) == session_labels {
+                root.insert(
+                    "desktopSessionWindows".into(),
+                    serde_json::to_value(&session).unwrap_or(serde_json::Value::Array(vec![])),
+                );
+            }
+            let Some(snapshot) = snapshot.as_ref() else {
+                return;
+            };
+            if let Some(identity) = identity.as_deref() {
+                apply_desktop_window_state_for_identity(root, identity, snapshot);
+            }
+            if label == "main" {
+                root.insert(
+                    "desktopWindowState".into(),
+                    serde_json::to_value(snapshot).unwrap_or(serde_json::Value::Null),
+                );
+            }
+        });
+        if let Err(err) = result {
+            log::warn!("[desktop] failed to persist window state for {label}: {err}");
+        }
+    });
+}
+
+/// Creation order of a window label (`main` = 1, `main-N` = N).
+fn window_label_index(label: &str) -> u64 {
+    if label == "main" {
+        return 1;
+    }
+    label
+        .strip_prefix("main-")
+        .and_then(|n| n.parse::<u64>().ok())
+        .unwrap_or(u64::MAX)
+}
+
+/// Pin window `label` to the host serving `url`. URLs that are neither local nor a
+/// configured host (such as a login redirect, or the previous local origin once the
+/// server has moved) leave the pin as it was.
+fn track_window_host(app: &tauri::AppHandle, label: &str, url: &url::Url) {
+    let host_id = if is_local_window_url(app, url) {
+        Some(LOCAL_HOST_ID.to_string())
+    } else {
+        normalize_host_url(url.as_str()).and_then(|host_url| {
+            read_desktop_hosts_config_from_disk()
+                .hosts
+                .into_iter()
+                .find(|host| host.url == host_url)
+                .map(|host| host.id)
+        })
+    };
+    let Some(host_id) = host_id else {
+        return;
+    };
+
+    if let Some(state) = app.try_state::<DesktopUiInjectionState>() {
+        if let Ok(mut window_hosts) = state.window_hosts.lock() {
+            window_hosts.insert(label.to_string(), host_id);
+        }
+    }
+}
+
+fn window_host_id(app: &tauri::AppHandle, label: &str) -> Option<String> {
+    app.try_state::<DesktopUiInjectionState>()
+        .and_then(|state| state.window_hosts.lock().ok()?.get(label).cloned())
+}
+
+/// Stable workspace identity for a window, `<host id>:<project path>` (or just the
+/// host id before the window reported a project). Unlike the `main-N` label it
+/// survives relaunches, so saved window state can be matched back to a window.
+/// The UI reports the project via `desktop_set_window_project` whenever its active
+/// project changes. Two windows on the same host and project share an identity.
+fn window_identity(app: &tauri::AppHandle, label: &str) -> Option<String> {
+    let host_id = window_host_id(app, label)?;
+    let project = app
+        .try_state::<DesktopUiInjectionState>()
+        .and_then(|state| state.window_projects.lock().ok()?.get(label).cloned());
+    Some(match project {
+        Some(project) => format!("{host_id}:{project}"),
+        None => host_id,
+    })
+}
+
+/// Record the calling window's active project (`None` when no project is open).
+#[tauri::command]
+fn desktop_set_window_project(webview: tauri::Webview, path: Option<String>) -> Result<(), String> {
+    let app = webview.app_handle();
+    let state = app
+        .try_state::<DesktopUiInjectionState>()
+        .ok_or_else(|| "Desktop state unavailable".to_string())?;
+    let mut projects = state.window_projects.lock().map_err(|_| "Window projects lock poisoned".to_string())?;
+
+    let path = path.map(|raw| raw.trim().to_string()).filter(|raw| !raw.is_empty());
+    match path {
+        Some(path) => {
+            let normalized = expand_home_path(&path).to_string_lossy().trim_end_matches(['/', '\\']).to_string();
+            projects.insert(webview.label().to_string(), if normalized.is_empty() { path } else { normalized });
+        }
+        None => {
+            projects.remove(webview.label());
+        }
+    }
+    drop(projects);
+
+    // The window's identity changed; keep the saved session in step.
+    persist_window_session(app);
+    Ok(())
+}
+
+/// Workspace identity of window `label`; `None` while its host is unknown.
+#[tauri::command]
+fn desktop_window_identity(app: tauri::AppHandle, label: String) -> Result<Option<String>, String> {
+    Ok(window_identity(&app, &label))
+}
+
+/// Host id (`local` or a configured host id) the window is pinned to, or `None` for
+/// windows opened at an arbitrary URL.
+#[tauri::command]
+fn desktop_get_window_host(app: tauri::AppHandle, label: String) -> Result<Option<String>, String> {
+    Ok(window_host_id(&app, &label))
+}
+
+/// Same scheme and port, and the same host once `localhost`/`127.0.0.1`/`[::1]`
+/// are treated as equivalent.
+fn same_origin_modulo_loopback(a: &url::Url, b: &url::Url) -> bool {
+    fn is_loopback(url: &url::Url) -> bool {
+        match url.host() {
+            Some(url::Host::Domain(domain)) => domain.eq_ignore_ascii_case("localhost"),
+            Some(url::Host::Ipv4(ip)) => ip.is_loopback(),
+            Some(url::Host::Ipv6(ip)) => ip.is_loopback(),
+            None => false,
+        }
+    }
+
+    if a.scheme() != b.scheme() || a.port_or_known_default() != b.port_or_known_default() {
+        return false;
+    }
+    (is_loopback(a) && is_loopback(b)) || a.origin() == b.origin()
+}
+
+/// Whether a window URL points at the local server (sidecar or dev UI origin).
+fn is_local_window_url(app: &tauri::AppHandle, url: &url::Url) -> bool {
+    let local_origin = app
+        .try_state::<DesktopUiInjectionState>()
+        .and_then(|state| state.local_origin.lock().expect("desktop local origin mutex").clone());
+    let local_url = app
+        .try_state::<SidecarState>()
+        .and_then(|state| state.url.lock().expect("sidecar url mutex").clone());
+
+    [local_origin, local_url]
+        .into_iter()
+        .flatten()
+        .filter_map(|local| url::Url::parse(&local).ok())
+        .any(|local| same_origin_modulo_loopback(&local, url))
+}
+
+/// Whether `url` is served by the running local server, for badging the "Local"
+/// entry in the host switcher.
+#[tauri::command]
+fn desktop_is_local_host(app: tauri::AppHandle, url: String) -> Result<bool, String> {
+    let parsed = url::Url::parse(url.trim()).map_err(|err| format!("Invalid URL: {err}"))?;
+    Ok(is_local_window_url(&app, &parsed))
+}
+
+fn open_window_labels(app: &tauri::AppHandle) -> HashSet<String> {
+    app.webview_windows().into_keys().collect()
+}
+
+/// Snapshot every open window (URL + geometry) in creation order.
+fn capture_window_session(app: &tauri::AppHandle) -> Vec<DesktopSessionWindow> {
+    let mut windows: Vec<_> = app.webview_windows().into_iter().collect();
+    windows.sort_by_key(|(label, _)| window_label_index(label));
+
+    let mut session = Vec::new();
+    for (label, webview) in windows {
+        let Ok(url) = webview.url() else {
+            continue;
+        };
+        if url.scheme() != "http" && url.scheme() != "https" {
+            continue;
+        }
+        let state = capture_window_state(&webview.as_ref().window());
+        session.push(DesktopSessionWindow {
+            local: is_local_window_url(app, &url),
+            url: url.to_string(),
+            state,
+            identity: window_identity(app, &label),
+        });
+    }
+    session
+}
+
+fn persist_window_session(app: &tauri::AppHandle) {
+    if APP_EXITING.load(Ordering::SeqCst) {
+        return;
+    }
 
-        let overlap_width = right.min(monitor_right) - left.max(monitor_left);
-        let overlap_height = bottom.min(monitor_bottom) - top.max(monitor_top);
-        if overlap_width > 0.0 && overlap_height > 0.0 {
-            return true;
-        }
+    let session = capture_window_session(app);
+    if session.is_empty() {
+        return;
     }
 
-    false
+    if let Err(err) = write_desktop_session_windows_to_disk(&session) {
+        log::warn!("[desktop] failed to persist window session: {err}");
+    }
 }
 
-fn capture_window_state(window: &tauri::Window) -> Option<DesktopWindowState> {
-    let position = window.outer_position().ok()?;
-    let size = window.inner_size().ok()?;
-    let scale = window
-        .scale_factor()
-        .ok()
-        .filter(|value| value.is_finite() && *value > 0.0)
-        .unwrap_or(1.0);
-
-    Some(DesktopWindowState {
-        x: (position.x as f64 / scale).round() as i32,
-        y: (position.y as f64 / scale).round() as i32,
-        width: (size.width as f64 / scale).round().max(MIN_WINDOW_WIDTH as f64) as u32,
-        height: (size.height as f64 / scale).round().max(MIN_WINDOW_HEIGHT as f64) as u32,
-        maximized: window.is_maximized().unwrap_or(false),
-        fullscreen: window.is_fullscreen().unwrap_or(false),
-    })
+/// Rebase a saved local-window URL onto the current local UI URL, keeping the route.
+fn rebase_local_url(saved: &str, local_ui_url: &str) -> Option<String> {
+    let saved = url::Url::parse(saved).ok()?;
+    let mut rebased = url::Url::parse(local_ui_url).ok()?;
+    rebased.set_path(saved.path());
+    rebased.set_query(saved.query());
+    rebased.set_fragment(saved.fragment());
+    Some(rebased.to_string())
 }
 
-fn schedule_window_state_persist(window: tauri::Window, immediate: bool) {
-    if window.label() != "main" {
-        return;
+/// Recreate the windows from the last session. Returns the number of windows opened.
+async fn restore_window_session(
+    app: &tauri::AppHandle,
+    session: Vec<DesktopSessionWindow>,
+    local_ui_url: &str,
+    local_origin: &str,
+) -> usize {
+    const STAGGER_OFFSET: i32 = 30;
+
+    let mut probed: HashMap<String, bool> = HashMap::new();
+    let mut used_positions: HashSet<(i32, i32)> = HashSet::new();
+    let mut opened = 0;
+    let identity_states = read_desktop_window_states_from_disk();
+    let mut identity_counts: HashMap<String, usize> = HashMap::new();
+    for identity in session.iter().filter_map(|entry| entry.identity.clone()) {
+        *identity_counts.entry(identity).or_insert(0) += 1;
     }
 
-    let app = window.app_handle().clone();
-    let label = window.label().to_string();
-    let revision = {
-        let Some(state) = app.try_state::<WindowGeometryDebounceState>() else {
-            return;
+    for entry in session {
+        let target_url = if entry.local {
+            rebase_local_url(&entry.url, local_ui_url).unwrap_or_else(|| local_ui_url.to_string())
+        } else if is_local_only() {
+            local_ui_url.to_string()
+        } else {
+            let Some(host_url) = normalize_host_url(&entry.url) else {
+                continue;
+            };
+            let reachable = match probed.get(&host_url) {
+                Some(reachable) => *reachable,
+                None => {
+                    let reachable = desktop_host_probe(host_url.clone())
+                        .await
+                        .map(|probe| probe.status != "unreachable")
+                        .unwrap_or(false);
+                    if !reachable {
+                        log::warn!("[desktop] restored window host unreachable ({}), using local", host_url);
+                        if let Some(state) = app.try_state::<DesktopUiInjectionState>() {
+                            state.unreachable_hosts.lock().expect("unreachable hosts mutex").insert(host_url.clone());
+                        }
+                    }
+                    probed.insert(host_url.clone(), reachable);
+                    reachable
+                }
+            };
+            if reachable {
+                entry.url.clone()
+            } else {
+                local_ui_url.to_string()
+            }
         };
-        let mut guard = state.revisions.lock().expect("window geometry debounce mutex");
-        let next = guard.get(&label).copied().unwrap_or(0).saturating_add(1);
-        guard.insert(label.clone(), next);
-        next
-    };
 
-    tauri::async_runtime::spawn(async move {
-        if !immediate {
-            tokio::time::sleep(Duration::from_millis(WINDOW_STATE_DEBOUNCE_MS)).await;
-        }
+        // The identity's own state keeps its mode and normal geometry independently,
+        // but only an identity unique within the session is specific to this window.
+        let saved_state = entry
+            .identity
+            .as_ref()
+            .filter(|identity| identity_counts.get(identity.as_str()).copied() == Some(1))
+            .and_then(|identity| identity_states.get(identity).cloned())
+            .or(entry.state);
+
+        // Stagger windows that were saved at identical coordinates.
+        let state = saved_state.map(|mut state| {
+            while !used_positions.insert((state.x, state.y)) {
+                state.x = state.x.saturating_add(STAGGER_OFFSET);
+                state.y = state.y.saturating_add(STAGGER_OFFSET);
+            }
+            state
+        });
 
-        let is_latest = app
-            .try_state::<WindowGeometryDebounceState>()
-            .map(|state| {
-                state
-                    .revisions
-                    .lock()
-                    .map(|guard| guard.get(&label).copied() == Some(revision))
-                    .unwrap_or(false)
-            })
-            .unwrap_or(false);
-        if !is_latest {
-            return;
+        match create_window(app, &target_url, local_origin, state) {
+            Ok(()) => opened += 1,
+            Err(err) => log::error!("[desktop] failed to restore window ({}): {err}", target_url),
         }
+    }
 
-        let Some(snapshot) = capture_window_state(&window) else {
-            return;
+    opened
+}
+
+/// Logical position for a new window, offset from the focused window so it
+/// doesn't open exactly on top of it. Wraps back toward the monitor's top-left
+/// when the offset window would run off the monitor.
+fn cascade_window_position(app: &tauri::AppHandle) -> Option<(f64, f64)> {
+    let windows = app.webview_windows();
+    let focused = windows
+        .values()
+        .find(|window| window.is_focused().unwrap_or(false))
+        .or_else(|| windows.get("main"))
+        .or_else(|| windows.values().next())?;
+
+    let scale = focused
+        .scale_factor()
+        .ok()
+        .filter(|value| value.is_finite() && *value > 0.0)
+        .unwrap_or(1.0);
+    let position = focused.outer_position().ok()?;
+    let mut x = position.x as f64 / scale + WINDOW_CASCADE_OFFSET;
+    let mut y = position.y as f64 / scale + WINDOW_CASCADE_OFFSET;
+
+    if let Ok(Some(monitor)) = focused.current_monitor() {
+        let monitor_scale = monitor.scale_factor();
+        let monitor_scale = if monitor_scale.is_finite() && monitor_scale > 0.0 {
+            monitor_scale
+        } else {
+            1.0
         };
+        let monitor_left = monitor.position().x as f64 / monitor_scale;
+        let monitor_top = monitor.position().y as f64 / monitor_scale;
+        let monitor_right = monitor_left + monitor.size().width as f64 / monitor_scale;
+        let monitor_bottom = monitor_top + monitor.size().height as f64 / monitor_scale;
 
-        if let Err(err) = write_desktop_window_state_to_disk(&snapshot) {
-            log::warn!("[desktop] failed to persist window geometry: {err}");
+        if x + DEFAULT_WINDOW_WIDTH > monitor_right {
+            x = monitor_left + WINDOW_CASCADE_OFFSET;
         }
-    });
+        if y + DEFAULT_WINDOW_HEIGHT > monitor_bottom {
+            y = monitor_top + WINDOW_CASCADE_OFFSET;
+        }
+    }
+
+    Some((x, y))
 }
 
 /// Create a new window with a unique label, pointing at the given URL.
-fn create_window(app: &tauri::AppHandle, url: &str, local_origin: &str, restore_geometry: bool) -> Result<()> {
+///
+/// `restored_state` is the saved geometry to apply, if any; it is ignored when it
+/// no longer overlaps a connected monitor.
+fn create_window(
+    app: &tauri::AppHandle,
+    url: &str,
+    local_origin: &str,
+    restored_state: Option<DesktopWindowState>,
+) -> Result<()> {
     let parsed = url::Url::parse(url).map_err(|err| anyhow!("Invalid URL: {err}"))?;
     let label = next_window_label();
 
-    let init_script = build_init_script(local_origin);
+    // Computed before the new window exists so it cascades from the currently focused one.
+    let cascade_position = if restored_state.is_none() {
+        cascade_window_position(app)
+    } else {
+        None
+    };
+
+    let init_script = build_init_script(app, local_origin);
 
     // Store the init script and local origin so new windows and page reloads can reuse it.
     if let Some(state) = app.try_state::<DesktopUiInjectionState>() {
@@ -2144,19 +5087,20 @@ fn create_window(app: &tauri::AppHandle, url: &str, local_origin: &str, restore_
         *state.local_origin.lock().expect("desktop local origin mutex") = Some(local_origin.to_string());
     }
 
-    let restored_state = if restore_geometry {
-        read_desktop_window_state_from_disk()
-    } else {
-        None
-    };
+    // The per-host snippet only goes into this window's script, never the shared one.
+    let mut window_script = init_script;
+    if let Some(snippet) = host_init_script_for_url(&parsed) {
+        log::info!("[desktop] applying host init script to {label}");
+        window_script.push_str(&snippet);
+    }
 
-    let mut builder = WebviewWindowBuilder::new(app, &label, WebviewUrl::External(parsed))
+    let mut builder = WebviewWindowBuilder::new(app, &label, WebviewUrl::External(parsed.clone()))
         .title("OpenChamber")
-        .inner_size(1280.0, 800.0)
+        .inner_size(DEFAULT_WINDOW_WIDTH, DEFAULT_WINDOW_HEIGHT)
         .min_inner_size(MIN_WINDOW_WIDTH as f64, MIN_WINDOW_HEIGHT as f64)
         .decorations(true)
         .visible(false)
-        .initialization_script(&init_script)
+        .initialization_script(&window_script)
         .background_throttling(BackgroundThrottlingPolicy::Disabled)
         ;
 
@@ -2171,31 +5115,245 @@ fn create_window(app: &tauri::AppHandle, url: &str, local_origin: &str, restore_
         builder = builder
             .inner_size(restored_width as f64, restored_height as f64)
             .position(state.x as f64, state.y as f64);
+    } else if let Some((x, y)) = cascade_position {
+        builder = builder.position(x, y);
     }
 
     #[cfg(target_os = "macos")]
     {
-        builder = builder
-            .hidden_title(true)
-            .title_bar_style(tauri::TitleBarStyle::Overlay)
-            .traffic_light_position(tauri::Position::Logical(tauri::LogicalPosition { x: 17.0, y: 26.0 }));
+        let title_bar = read_desktop_title_bar_from_disk();
+        builder = match title_bar.style {
+            TitleBarMode::Overlay => builder
+                .hidden_title(true)
+                .title_bar_style(tauri::TitleBarStyle::Overlay)
+                .traffic_light_position(tauri::Position::Logical(tauri::LogicalPosition {
+                    x: title_bar.traffic_light_inset.x,
+                    y: title_bar.traffic_light_inset.y,
+                })),
+            TitleBarMode::Visible => builder.title_bar_style(tauri::TitleBarStyle::Visible),
+        };
     }
 
     let window = builder.build()?;
+    track_window_host(app, &label, &parsed);
 
     if let Some(state) = restored_state.as_ref().filter(|_| apply_restored_state) {
-        if state.maximized || state.fullscreen {
+        if state.fullscreen {
+            let _ = window.set_fullscreen(true);
+        } else if state.maximized {
             let _ = window.maximize();
         }
     }
 
-    let _ = window.show();
-    let _ = window.set_focus();
+    let deferred = read_desktop_show_when_ready_from_disk()
+        && app
+            .try_state::<DesktopUiInjectionState>()
+            .map(|state| state.pending_reveal.lock().expect("pending reveal mutex").insert(label.clone()))
+            .unwrap_or(false);
+    if deferred {
+        let handle = app.clone();
+        let pending_label = label.clone();
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(WINDOW_READY_TIMEOUT).await;
+            if reveal_window(&handle, &pending_label) {
+                log::warn!("[desktop] {pending_label} did not report ready, showing anyway");
+            }
+        });
+    } else {
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+
+    emit_window_lifecycle(app, "openchamber:window-opened", &label, None);
+    persist_window_session(app);
+
+    Ok(())
+}
+
+/// Show and focus a window held back by `desktopShowWhenReady`. Returns false if it
+/// was not pending (already shown, or never deferred).
+fn reveal_window(app: &tauri::AppHandle, label: &str) -> bool {
+    let was_pending = app
+        .try_state::<DesktopUiInjectionState>()
+        .map(|state| state.pending_reveal.lock().expect("pending reveal mutex").remove(label))
+        .unwrap_or(false);
+    if !was_pending {
+        return false;
+    }
+    if let Some(window) = app.get_webview_window(label) {
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+    true
+}
 
+/// Called by the page after its first render so a window deferred by
+/// `desktopShowWhenReady` appears already rendered. Only reveals the calling window.
+#[tauri::command]
+fn desktop_window_ready(webview: tauri::Webview) -> Result<(), String> {
+    reveal_window(webview.app_handle(), webview.label());
     Ok(())
 }
 
-/// Open a new window pointed at the default host (local or configured default).
+/// Point every window showing the local UI at the current local server, keeping its
+/// path/query. Intended for after the sidecar comes back on a different port.
+/// Returns the number of windows navigated.
+#[tauri::command]
+fn desktop_reload_local_windows(app: tauri::AppHandle) -> Result<usize, String> {
+    let local_url = app
+        .try_state::<SidecarState>()
+        .and_then(|state| state.url.lock().expect("sidecar url mutex").clone())
+        .ok_or_else(|| "Local server is not running".to_string())?;
+
+    // Release builds serve the UI from the sidecar itself, so its origin is the new
+    // local origin; refresh the injected script to match.
+    if !cfg!(debug_assertions) {
+        let new_origin = url::Url::parse(&local_url)
+            .map(|u| u.origin().ascii_serialization())
+            .map_err(|err| err.to_string())?;
+        let script = build_init_script(&app, &new_origin);
+        if let Some(state) = app.try_state::<DesktopUiInjectionState>() {
+            *state.script.lock().expect("desktop ui injection mutex") = Some(script);
+            *state.local_origin.lock().expect("desktop local origin mutex") = Some(new_origin);
+        }
+    }
+
+    let (_, local_ui_url) = resolve_local_ui_url(&app).ok_or_else(|| "Local server is not ready".to_string())?;
+    let mut reloaded = 0;
+    for (label, window) in app.webview_windows() {
+        if window_host_id(&app, &label).as_deref() != Some(LOCAL_HOST_ID) {
+            continue;
+        }
+        let target = window
+            .url()
+            .ok()
+            .and_then(|current| rebase_local_url(current.as_str(), &local_ui_url))
+            .unwrap_or_else(|| local_ui_url.clone());
+        let Ok(target) = url::Url::parse(&target) else {
+            continue;
+        };
+        match window.navigate(target) {
+            Ok(()) => reloaded += 1,
+            Err(err) => log::warn!("[desktop] failed to reload {label} against local server: {err}"),
+        }
+    }
+
+    log::info!("[desktop] reloaded {reloaded} local window(s) at {local_ui_url}");
+    Ok(reloaded)
+}
+
+/// Periodically re-probe the hosts of open remote windows. When a window's host
+/// stops answering, mark it unreachable and emit `openchamber:host-lost` with the
+/// window label so the UI can offer to reconnect or switch to local.
+fn spawn_host_monitor(app: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        // Window labels already reported as lost, so each loss is emitted once.
+        let mut lost_labels: HashSet<String> = HashSet::new();
+
+        loop {
+            tokio::time::sleep(HOST_MONITOR_INTERVAL).await;
+            if APP_EXITING.load(Ordering::SeqCst) {
+                break;
+            }
+
+            let mut windows_by_host: HashMap<String, Vec<String>> = HashMap::new();
+            for (label, webview) in app.webview_windows() {
+                let Ok(url) = webview.url() else {
+                    continue;
+                };
+                if is_local_window_url(&app, &url) {
+                    continue;
+                }
+                if let Some(host_url) = normalize_host_url(url.as_str()) {
+                    windows_by_host.entry(host_url).or_default().push(label);
+                }
+            }
+            lost_labels.retain(|label| windows_by_host.values().any(|labels| labels.contains(label)));
+
+            // Spawn every probe up front so the per-host timeouts overlap.
+            let probes: Vec<_> = windows_by_host
+                .into_iter()
+                .map(|(host_url, labels)| {
+                    tauri::async_runtime::spawn(async move {
+                        let reachable = desktop_host_probe(host_url.clone())
+                            .await
+                            .map(|probe| probe.status != "unreachable")
+                            .unwrap_or(false);
+                        (host_url, labels, reachable)
+                    })
+                })
+                .collect();
+
+            for probe in probes {
+                let Ok((host_url, labels, reachable)) = probe.await else {
+                    continue;
+                };
+
+                if reachable {
+                    // A recovered host is fine for new windows again.
+                    if let Some(state) = app.try_state::<DesktopUiInjectionState>() {
+                        state.unreachable_hosts.lock().expect("unreachable hosts mutex").remove(&host_url);
+                    }
+                    for label in &labels {
+                        lost_labels.remove(label);
+                    }
+                    continue;
+                }
+
+                if let Some(state) = app.try_state::<DesktopUiInjectionState>() {
+                    state.unreachable_hosts.lock().expect("unreachable hosts mutex").insert(host_url.clone());
+                }
+
+                for label in labels {
+                    if lost_labels.insert(label.clone()) {
+                        log::warn!("[desktop] window {} lost its host ({})", label, host_url);
+                        dispatch_host_lost(&app, &label, &host_url);
+                    }
+                }
+            }
+        }
+    });
+}
+
+fn dispatch_host_lost(app: &tauri::AppHandle, label: &str, host_url: &str) {
+    let payload = serde_json::json!({ "label": label, "url": host_url });
+    let _ = app.emit("openchamber:host-lost", payload.clone());
+
+    // The lost window itself is showing an error page, so let every window know.
+    let event = serde_json::to_string("openchamber:host-lost")
+        .unwrap_or_else(|_| "\"openchamber:host-lost\"".into());
+    let detail = serde_json::to_string(&payload).unwrap_or_else(|_| "{}".into());
+    let script = format!("window.dispatchEvent(new CustomEvent({event}, {{ detail: {detail} }}));");
+    eval_in_all_windows(app, &script);
+}
+
+fn should_confirm_last_window_close(app: &tauri::AppHandle) -> bool {
+    !APP_EXITING.load(Ordering::SeqCst)
+        && !QUIT_CONFIRMED.load(Ordering::SeqCst)
+        && app.webview_windows().len() == 1
+        && read_desktop_confirm_quit_from_disk()
+}
+
+/// Ask before the last window closes (and the app quits); closes it again on confirm.
+fn confirm_last_window_close(window: tauri::Window) {
+    use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind};
+
+    let app = window.app_handle().clone();
+    app.dialog()
+        .message("Closing the last window will quit OpenChamber.")
+        .title("Quit OpenChamber?")
+        .kind(MessageDialogKind::Warning)
+        .buttons(MessageDialogButtons::OkCancelCustom("Quit".to_string(), "Cancel".to_string()))
+        .parent(&window)
+        .show(move |confirmed| {
+            if confirmed {
+                QUIT_CONFIRMED.store(true, Ordering::SeqCst);
+                let _ = window.close();
+            }
+        });
+}
+
+/// Open a new window at the host chosen by `desktopNewWindowHost` (default host by default).
 ///
 /// Known multi-window limitations (acceptable for v1):
 ///
@@ -2215,17 +5373,58 @@ fn create_window(app: &tauri::AppHandle, url: &str, local_origin: &str, restore_
 /// - **Startup race**: If called before the sidecar finishes starting (local_origin
 ///   not yet set), this function silently bails with a log warning. The user sees
 ///   no feedback from clicking the dock icon during the startup window (~0-20s).
+///
+/// Calls within `NEW_WINDOW_DEBOUNCE` of the last window opened here are dropped, so
+/// a double-clicked dock icon or a Reopen racing the menu item yields one window.
+/// Attempts that fail (e.g. before the sidecar is up) don't start the interval.
 fn open_new_window(app: &tauri::AppHandle) {
-    let local_origin = app
-        .try_state::<DesktopUiInjectionState>()
-        .and_then(|state| state.local_origin.lock().expect("desktop local origin mutex").clone());
+    {
+        let last = LAST_NEW_WINDOW_AT.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if last.is_some_and(|at| at.elapsed() < NEW_WINDOW_DEBOUNCE) {
+            log::info!("[desktop] ignoring new window request within debounce interval");
+            return;
+        }
+    }
 
-    let Some(local_origin) = local_origin else {
+    let Some((local_origin, local_ui_url)) = resolve_local_ui_url(app) else {
         log::warn!("[desktop] cannot open new window: local origin not yet known (sidecar may still be starting)");
         return;
     };
 
-    // Resolve the URL the same way as initial setup: default host or local.
+    // `desktopNewWindowHost` picks the host; unreachable hosts still fall back to local.
+    let host_id = match read_desktop_new_window_host_from_disk() {
+        NewWindowHost::Default => None,
+        NewWindowHost::Follow => app
+            .webview_windows()
+            .into_iter()
+            .find(|(_, window)| window.is_focused().unwrap_or(false))
+            .and_then(|(label, _)| window_host_id(app, &label)),
+        NewWindowHost::Pinned(host_id) => {
+            let known = host_id == LOCAL_HOST_ID
+                || read_desktop_hosts_config_from_disk().hosts.iter().any(|host| host.id == host_id);
+            if !known {
+                log::warn!("[desktop] desktopNewWindowHost names unknown host {host_id}, using default");
+            }
+            known.then_some(host_id)
+        }
+    };
+    let target_url = resolve_window_host_url(app, host_id.as_deref(), &local_ui_url);
+
+    match create_window(app, &target_url, &local_origin, None) {
+        Ok(_) => {
+            *LAST_NEW_WINDOW_AT.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) =
+                Some(std::time::Instant::now());
+        }
+        Err(err) => log::error!("[desktop] failed to create new window: {err}"),
+    }
+}
+
+/// The local origin and the URL local windows should load, once the sidecar is known.
+fn resolve_local_ui_url(app: &tauri::AppHandle) -> Option<(String, String)> {
+    let local_origin = app
+        .try_state::<DesktopUiInjectionState>()
+        .and_then(|state| state.local_origin.lock().expect("desktop local origin mutex").clone())?;
+
     let local_url = app
         .try_state::<SidecarState>()
         .and_then(|state| state.url.lock().expect("sidecar url mutex").clone())
@@ -2235,16 +5434,62 @@ fn open_new_window(app: &tauri::AppHandle) {
         // In dev mode, prefer the Vite dev server if it was used as local origin.
         local_origin.clone()
     } else {
-        local_url.clone()
+        local_url
+    };
+
+    Some((local_origin, local_ui_url))
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LocalServerInfo {
+    /// Local API server URL, once known.
+    url: Option<String>,
+    port: Option<u16>,
+    /// `"sidecar"` when this app spawned the server, `"dev"` when it reused an
+    /// already-running dev server.
+    source: Option<String>,
+    /// URL local windows load; the Vite dev server in debug builds when it was up.
+    ui_url: Option<String>,
+}
+
+#[tauri::command]
+fn desktop_get_local_server(app: tauri::AppHandle) -> Result<LocalServerInfo, String> {
+    let Some(state) = app.try_state::<SidecarState>() else {
+        return Ok(LocalServerInfo { url: None, port: None, source: None, ui_url: None });
     };
 
-    let mut target_url = local_ui_url.clone();
+    let url = state.url.lock().expect("sidecar url mutex").clone();
+    let has_child = state.child.lock().expect("sidecar mutex").is_some();
+    let port = url
+        .as_deref()
+        .and_then(|raw| url::Url::parse(raw).ok())
+        .and_then(|parsed| parsed.port_or_known_default());
+    let source = url
+        .as_ref()
+        .map(|_| if has_child { "sidecar" } else { "dev" }.to_string());
+    let ui_url = resolve_local_ui_url(&app).map(|(_, ui_url)| ui_url);
+
+    Ok(LocalServerInfo { url, port, source, ui_url })
+}
+
+/// Base URL for a new window at `host_id`. When `None`, the `OPENCHAMBER_SERVER_URL`
+/// override wins, then the configured default host. Hosts cached as unreachable
+/// fall back to local, as does everything in local-only mode.
+fn resolve_window_host_url(app: &tauri::AppHandle, host_id: Option<&str>, local_ui_url: &str) -> String {
+    let mut target_url = local_ui_url.to_string();
+    if is_local_only() {
+        return target_url;
+    }
 
     let cfg = read_desktop_hosts_config_from_disk();
-    if let Some(default_id) = cfg.default_host_id {
-        if default_id == LOCAL_HOST_ID {
-            target_url = local_ui_url.clone();
-        } else if let Some(host) = cfg.hosts.into_iter().find(|h| h.id == default_id) {
+    let env_target = if host_id.is_none() { server_url_override() } else { None };
+    if let Some(env_target) = env_target {
+        target_url = env_target;
+    } else if let Some(host_id) = host_id.map(|id| id.to_string()).or(cfg.default_host_id) {
+        if host_id == LOCAL_HOST_ID {
+            target_url = local_ui_url.to_string();
+        } else if let Some(host) = cfg.hosts.into_iter().find(|h| h.id == host_id) {
             target_url = host.url;
         }
     }
@@ -2257,14 +5502,28 @@ fn open_new_window(app: &tauri::AppHandle) {
             .unwrap_or(false);
 
         if is_cached_unreachable {
-            log::info!("[desktop] new window: default host ({}) cached as unreachable, using local", target_url);
-            target_url = local_ui_url;
+            log::info!("[desktop] new window: host ({}) cached as unreachable, using local", target_url);
+            target_url = local_ui_url.to_string();
         }
     }
 
-    if let Err(err) = create_window(app, &target_url, &local_origin, false) {
-        log::error!("[desktop] failed to create new window: {err}");
+    target_url
+}
+
+/// Append an in-app route (path plus optional query/fragment) to a host base URL,
+/// rejecting anything that would leave the host's origin.
+fn build_route_url(base_url: &str, route: &str) -> Result<String, String> {
+    let route = route.trim();
+    if !route.starts_with('/') || route.starts_with("//") || route.contains('\\') {
+        return Err("Route must be an absolute path like /sessions/123".to_string());
+    }
+
+    let base = url::Url::parse(base_url).map_err(|e| format!("Invalid host URL: {e}"))?;
+    let joined = base.join(route).map_err(|e| format!("Invalid route: {e}"))?;
+    if joined.origin() != base.origin() {
+        return Err("Route must stay on the host's origin".to_string());
     }
+    Ok(joined.to_string())
 }
 
 fn main() {
@@ -2274,7 +5533,12 @@ fn main() {
         .targets([
             tauri_plugin_log::Target::new(tauri_plugin_log::TargetKind::Stdout),
             tauri_plugin_log::Target::new(tauri_plugin_log::TargetKind::Webview),
-        ]);
+            tauri_plugin_log::Target::new(tauri_plugin_log::TargetKind::LogDir {
+                file_name: Some(LOG_FILE_NAME.to_string()),
+            }),
+        ])
+        .max_file_size(LOG_FILE_MAX_BYTES)
+        .rotation_strategy(tauri_plugin_log::RotationStrategy::KeepSome(LOG_FILES_KEPT));
 
     let builder = tauri::Builder::default()
         .manage(SidecarState::default())
@@ -2288,13 +5552,29 @@ fn main() {
         .plugin(tauri_plugin_notification::init())
         .plugin(tauri_plugin_updater::Builder::new().build())
         .plugin(log_builder.build())
-        .on_page_load(|window, _payload| {
+        .on_page_load(|window, payload| {
             if let Some(state) = window.app_handle().try_state::<DesktopUiInjectionState>() {
                 if let Ok(guard) = state.script.lock() {
                     if let Some(script) = guard.as_ref() {
                         let _ = window.eval(script);
                     }
                 }
+
+                if matches!(payload.event(), tauri::webview::PageLoadEvent::Finished) {
+                    dispatch_settings_corrupt(window);
+                    track_window_host(window.app_handle(), window.label(), payload.url());
+
+                    if let Some(host_url) = normalize_host_url(payload.url().as_str()) {
+                        let needs_auth = state
+                            .auth_required_hosts
+                            .lock()
+                            .map(|guard| guard.contains(&host_url))
+                            .unwrap_or(false);
+                        if needs_auth {
+                            dispatch_host_auth_required(window, &host_url);
+                        }
+                    }
+                }
             }
         })
         .menu(|app| {
@@ -2327,7 +5607,9 @@ fn main() {
                 }
 
                 if id == MENU_ITEM_CHECK_FOR_UPDATES_ID {
-                    dispatch_check_for_updates(app);
+                    if !is_updater_disabled() {
+                        dispatch_check_for_updates(app);
+                    }
                     return;
                 }
 
@@ -2441,6 +5723,37 @@ fn main() {
                 if let Some(state) = app.try_state::<WindowFocusState>() {
                     state.set_focused(&label, *focused);
                 }
+                emit_window_lifecycle(app, "openchamber:window-focus-changed", &label, Some(*focused));
+            }
+
+            // Dropped folders become projects; dropped files go to the page's attach flow
+            // as `openchamber:files-dropped`, so a folder is never attached as a file.
+            if let tauri::WindowEvent::DragDrop(tauri::DragDropEvent::Drop { paths, position }) = event {
+                let mut directories: Vec<String> = Vec::new();
+                let mut files: Vec<String> = Vec::new();
+                for path in paths {
+                    let value = path.to_string_lossy().to_string();
+                    if path.is_dir() {
+                        directories.push(value);
+                    } else {
+                        files.push(value);
+                    }
+                }
+                if !files.is_empty() {
+                    let _ = window.emit_to(
+                        label.as_str(),
+                        "openchamber:files-dropped",
+                        serde_json::json!({ "paths": files, "position": { "x": position.x, "y": position.y } }),
+                    );
+                }
+                if !directories.is_empty() {
+                    log::info!("[desktop] {} folder(s) dropped on {label}", directories.len());
+                    let _ = window.emit_to(
+                        label.as_str(),
+                        "openchamber:directories-dropped",
+                        serde_json::json!({ "paths": directories }),
+                    );
+                }
             }
 
             if let tauri::WindowEvent::Destroyed = event {
@@ -2448,12 +5761,26 @@ fn main() {
                 if let Some(state) = app.try_state::<WindowFocusState>() {
                     state.remove_window(&label);
                 }
+                if let Some(state) = app.try_state::<DesktopUiInjectionState>() {
+                    if let Ok(mut window_hosts) = state.window_hosts.lock() {
+                        window_hosts.remove(&label);
+                    }
+                    if let Ok(mut window_projects) = state.window_projects.lock() {
+                        window_projects.remove(&label);
+                    }
+                }
+
+                emit_window_lifecycle(app, "openchamber:window-closed", &label, None);
 
                 // If this was the last window, kill the sidecar and exit.
+                // The session keeps the last window so it is reopened next launch.
                 let remaining = app.webview_windows().len();
                 if remaining == 0 {
+                    APP_EXITING.store(true, Ordering::SeqCst);
                     kill_sidecar(app.clone());
                     app.exit(0);
+                } else {
+                    persist_window_session(app);
                 }
             }
 
@@ -2461,28 +5788,72 @@ fn main() {
                 schedule_window_state_persist(window.clone(), false);
             }
 
-            if matches!(event, tauri::WindowEvent::CloseRequested { .. }) {
+            if let tauri::WindowEvent::CloseRequested { api, .. } = event {
+                if should_confirm_last_window_close(app) {
+                    api.prevent_close();
+                    confirm_last_window_close(window.clone());
+                    return;
+                }
                 schedule_window_state_persist(window.clone(), true);
             }
         })
         .invoke_handler(tauri::generate_handler![
             desktop_notify,
+            desktop_test_notification,
+            desktop_notification_permission_status,
+            desktop_request_notification_permission,
             desktop_check_for_updates,
+            desktop_pending_update_info,
             desktop_download_and_install_update,
+            desktop_get_release_notes,
             desktop_restart,
+            desktop_quit,
+            desktop_collect_diagnostics,
+            desktop_get_app_info,
+            desktop_get_asset_info,
             desktop_new_window,
             desktop_new_window_at_url,
+            desktop_new_window_at_route,
+            desktop_get_local_server,
+            desktop_sidecar_status,
+            desktop_get_resolved_home,
+            desktop_resolve_opencode_binary,
+            desktop_reload_local_windows,
+            desktop_get_window_host,
+            desktop_set_window_project,
+            desktop_window_identity,
+            desktop_window_ready,
+            desktop_is_local_host,
             desktop_set_auto_worktree_menu,
+            desktop_get_menu_shortcuts,
+            desktop_set_menu_shortcut,
             desktop_open_path,
+            desktop_resolve_app_path,
+            desktop_reveal_in_file_manager,
+            desktop_open_log_directory,
             desktop_filter_installed_apps,
             desktop_get_installed_apps,
             desktop_fetch_app_icons,
+            desktop_fetch_app_icon,
             desktop_hosts_get,
             desktop_hosts_set,
+            desktop_title_bar_get,
+            desktop_title_bar_set,
+            desktop_compact_settings,
+            desktop_restore_windows_get,
+            desktop_restore_windows_set,
             desktop_host_probe,
+            desktop_host_probe_all,
+            desktop_clear_unreachable_hosts,
+            desktop_mark_host_reachable,
             desktop_read_file,
+            desktop_read_text_file,
         ])
         .setup(|app| {
+            let _ = SETTINGS_EVENT_APP.set(app.handle().clone());
+            check_settings_file();
+            spawn_host_monitor(app.handle().clone());
+
             let handle = app.handle().clone();
             tauri::async_runtime::spawn(async move {
                 let local_url = if cfg!(debug_assertions) {
@@ -2532,13 +5903,25 @@ fn main() {
                     .unwrap_or_else(|| local_ui_url.clone());
 
                 // Selected host: env override first, then desktop default host, else local.
-                let env_target = std::env::var("OPENCHAMBER_SERVER_URL")
-                    .ok()
-                    .and_then(|raw| normalize_server_url(&raw));
+                let local_only = is_local_only();
+                if local_only {
+                    log::info!("[desktop] local-only mode: ignoring host config");
+                }
+                let env_target = server_url_override().filter(|_| !local_only);
+
+                // Reopen the previous session's windows unless a server URL was forced via env.
+                if env_target.is_none() && read_desktop_restore_windows_from_disk() {
+                    let session = read_desktop_session_windows_from_disk();
+                    if !session.is_empty()
+                        && restore_window_session(&handle, session, &local_ui_url, &local_origin).await > 0
+                    {
+                        return;
+                    }
+                }
 
                 let mut initial_url = env_target.unwrap_or_else(|| local_ui_url.clone());
 
-                if initial_url == local_ui_url {
+                if initial_url == local_ui_url && !local_only {
                     let cfg = read_desktop_hosts_config_from_disk();
                     if let Some(default_id) = cfg.default_host_id {
                         if default_id == LOCAL_HOST_ID {
@@ -2552,6 +5935,21 @@ fn main() {
                 if initial_url != local_ui_url {
                     let failed_url = initial_url.clone();
                     match desktop_host_probe(initial_url.clone()).await {
+                        Ok(probe) if probe.status == "auth" => {
+                            if read_desktop_auth_fallback_from_disk() {
+                                log::warn!(
+                                    "[desktop] startup host requires auth ({}), falling back to local ({})",
+                                    initial_url,
+                                    local_ui_url
+                                );
+                                initial_url = local_ui_url.clone();
+                            } else {
+                                log::info!("[desktop] startup host requires auth ({})", initial_url);
+                                if let Some(state) = handle.try_state::<DesktopUiInjectionState>() {
+                                    state.auth_required_hosts.lock().expect("auth required hosts mutex").insert(failed_url);
+                                }
+                            }
+                        }
                         Ok(probe) if probe.status != "unreachable" => {}
                         Ok(_) => {
                             log::warn!(
@@ -2580,7 +5978,8 @@ fn main() {
                     }
                 }
 
-                if let Err(err) = create_window(&handle, &initial_url, &local_origin, true) {
+                let restored_state = read_desktop_window_state_from_disk();
+                if let Err(err) = create_window(&handle, &initial_url, &local_origin, restored_state) {
                     log::error!("[desktop] failed to create window: {err}");
                 }
             });
@@ -2596,6 +5995,9 @@ fn main() {
     app.run(|app_handle, event| {
         match event {
             tauri::RunEvent::ExitRequested { .. } => {
+                // Snapshot open windows before teardown starts closing them.
+                persist_window_session(app_handle);
+                APP_EXITING.store(true, Ordering::SeqCst);
                 // Best-effort cleanup; never block shutdown.
                 kill_sidecar(app_handle.clone());
             }
@@ -2613,3 +6015,25 @@ fn main() {
         }
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tauri::menu::{MenuBuilder, MenuItem, SubmenuBuilder};
+
+    #[test]
+    fn find_menu_item_searches_nested_submenus() {
+        let app = tauri::test::mock_app();
+        let nested = MenuItem::with_id(&app, "nested", "Nested", true, Some("Cmd+K")).unwrap();
+        let inner = SubmenuBuilder::new(&app, "Inner").item(&nested).build().unwrap();
+        let outer = SubmenuBuilder::new(&app, "Outer").item(&inner).build().unwrap();
+        let menu = MenuBuilder::new(&app).item(&outer).build().unwrap();
+
+        // The top-level lookup misses it, which is why the walk exists.
+        assert!(menu.get("nested").is_none());
+
+        let found = find_menu_item(menu.items().unwrap(), "nested").expect("nested item");
+        assert_eq!(found.id(), "nested");
+        assert!(find_menu_item(menu.items().unwrap(), "missing").is_none());
+    }
+}
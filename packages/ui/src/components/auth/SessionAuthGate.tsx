@@ -2,7 +2,7 @@ import React from 'react';
 import { RiLockLine, RiLockUnlockLine, RiLoader4Line } from '@remixicon/react';
 import { Button } from '@/components/ui/button';
 import { Input } from '@/components/ui/input';
-import { isDesktopShell, isVSCodeRuntime } from '@/lib/desktop';
+import { isDesktopLocalOnly, isDesktopShell, isVSCodeRuntime } from '@/lib/desktop';
 import { syncDesktopSettings, initializeAppearancePreferences } from '@/lib/persistence';
 import { applyPersistedDirectoryPreferences } from '@/lib/directoryPersistence';
 import { DesktopHostSwitcherInline } from '@/components/desktop/DesktopHostSwitcher';
@@ -122,7 +122,10 @@ const clearTokenFromUrl = () => {
 export const SessionAuthGate: React.FC<SessionAuthGateProps> = ({ children }) => {
   const vscodeRuntime = React.useMemo(() => isVSCodeRuntime(), []);
   const skipAuth = vscodeRuntime;
-  const showHostSwitcher = React.useMemo(() => isDesktopShell() && !vscodeRuntime, [vscodeRuntime]);
+  const showHostSwitcher = React.useMemo(
+    () => isDesktopShell() && !isDesktopLocalOnly() && !vscodeRuntime,
+    [vscodeRuntime]
+  );
   const [state, setState] = React.useState<GateState>(() => (skipAuth ? 'authenticated' : 'pending'));
   const [password, setPassword] = React.useState('');
   const [isSubmitting, setIsSubmitting] = React.useState(false);
@@ -1532,107 +1532,107 @@ export const ChatInput: React.FC<ChatInputProps> = ({ onOpenSettings, scrollToBo
         }
     };
 
-    // Tauri desktop: handle native file drops via onDragDropEvent
+    // Tauri desktop: native drags drive the highlight via onDragDropEvent; the drop itself
+    // arrives as `openchamber:files-dropped`, which carries files only (dropped folders
+    // are added as projects by the shell and never reach the attach flow).
     React.useEffect(() => {
         if (!isTauriShell()) return;
         let cancelled = false;
-        let unlisten: (() => void) | null = null;
+        const unlisteners: Array<() => void> = [];
+
+        const isInDropZone = (x?: number, y?: number): boolean => {
+            const zone = dropZoneRef.current;
+            if (!zone || typeof x !== 'number' || typeof y !== 'number') return false;
+            const rect = zone.getBoundingClientRect();
+            if (x >= rect.left && x <= rect.right && y >= rect.top && y <= rect.bottom) return true;
+            // Handle retina displays where Tauri might report physical pixels
+            if (window.devicePixelRatio > 1) {
+                const sx = x / window.devicePixelRatio;
+                const sy = y / window.devicePixelRatio;
+                return sx >= rect.left && sx <= rect.right && sy >= rect.top && sy <= rect.bottom;
+            }
+            return false;
+        };
+
+        const attachDroppedPaths = async (paths: string[]) => {
+            let attachedCount = 0;
+            for (const path of paths) {
+                const sizeBefore = useSessionStore.getState().attachedFiles.length;
+                try {
+                    const normalizedPath = normalizeDroppedPath(path);
+                    const fileName = normalizedPath.split(/[\\/]/).pop() || normalizedPath;
+                    let file: File;
+
+                    // In desktop shell on remote origin, local file paths are not readable via /api/fs/raw.
+                    // Read bytes from local machine via Tauri command.
+                    if (isTauriShell() && !isDesktopLocalOriginActive()) {
+                        const { invoke } = await import('@tauri-apps/api/core');
+                        const result = await invoke<{ mime: string; base64: string }>('desktop_read_file', { path: normalizedPath });
+                        const byteCharacters = atob(result.base64);
+                        const byteNumbers = new Array(byteCharacters.length);
+                        for (let i = 0; i < byteCharacters.length; i++) {
+                            byteNumbers[i] = byteCharacters.charCodeAt(i);
+                        }
+                        const byteArray = new Uint8Array(byteNumbers);
+                        const blob = new Blob([byteArray], { type: result.mime || 'application/octet-stream' });
+                        file = new File([blob], fileName, { type: result.mime || 'application/octet-stream' });
+                    } else {
+                        const response = await fetch(`/api/fs/raw?path=${encodeURIComponent(normalizedPath)}`);
+                        if (!response.ok) {
+                            throw new Error(`Failed to read dropped file (${response.status})`);
+                        }
+                        const blob = await response.blob();
+                        file = new File([blob], fileName, { type: blob.type || 'application/octet-stream' });
+                    }
+
+                    await addAttachedFile(file);
+                    const sizeAfter = useSessionStore.getState().attachedFiles.length;
+                    if (sizeAfter > sizeBefore) attachedCount++;
+                } catch (error) {
+                    console.error('Failed to attach dropped file:', path, error);
+                    toast.error(`Failed to attach ${path.split(/[\\/]/).pop() || 'file'}`);
+                }
+            }
+            if (attachedCount > 0) {
+                toast.success(`Attached ${attachedCount} file${attachedCount > 1 ? 's' : ''}`);
+            }
+        };
 
         void (async () => {
             try {
                 const { getCurrentWebviewWindow } = await import('@tauri-apps/api/webviewWindow');
                 const webviewWindow = getCurrentWebviewWindow();
-                const removeListener = await webviewWindow.onDragDropEvent(async (event) => {
-                    if (!canAcceptDropRef.current) return;
-
+                const removeDragListener = await webviewWindow.onDragDropEvent((event) => {
                     const payload = (event as { payload?: unknown }).payload;
                     if (!payload || typeof payload !== 'object') return;
 
-                    const typed = payload as { type?: string; paths?: string[]; position?: { x?: number; y?: number } };
-                    const type = typed.type;
-                    const x = typed.position?.x;
-                    const y = typed.position?.y;
-
-                    // Check if drop is inside the chat input area
-                    const zone = dropZoneRef.current;
-                    let inZone = false;
-                    if (zone && typeof x === 'number' && typeof y === 'number') {
-                        const rect = zone.getBoundingClientRect();
-                        inZone = x >= rect.left && x <= rect.right && y >= rect.top && y <= rect.bottom;
-                        // Handle retina displays where Tauri might report physical pixels
-                        if (!inZone && window.devicePixelRatio > 1) {
-                            const sx = x / window.devicePixelRatio;
-                            const sy = y / window.devicePixelRatio;
-                            inZone = sx >= rect.left && sx <= rect.right && sy >= rect.top && sy <= rect.bottom;
-                        }
-                    }
-
-                    if (type === 'enter' || type === 'over') {
-                        setIsDragging(inZone);
-                        return;
-                    }
-                    if (type === 'leave') {
-                        setIsDragging(false);
+                    const typed = payload as { type?: string; position?: { x?: number; y?: number } };
+                    if (typed.type === 'enter' || typed.type === 'over') {
+                        setIsDragging(canAcceptDropRef.current && isInDropZone(typed.position?.x, typed.position?.y));
                         return;
                     }
-                    if (type === 'drop') {
-                        setIsDragging(false);
-                        if (!inZone) return;
+                    setIsDragging(false);
+                });
+                unlisteners.push(removeDragListener);
+
+                const removeDropListener = await webviewWindow.listen<{ paths?: unknown; position?: { x?: number; y?: number } }>(
+                    'openchamber:files-dropped',
+                    (event) => {
+                        if (!canAcceptDropRef.current) return;
+                        if (!isInDropZone(event.payload?.position?.x, event.payload?.position?.y)) return;
 
-                        const paths = Array.isArray(typed.paths)
-                            ? typed.paths.filter((p): p is string => typeof p === 'string')
+                        const paths = Array.isArray(event.payload?.paths)
+                            ? event.payload.paths.filter((p): p is string => typeof p === 'string')
                             : [];
                         if (paths.length === 0) return;
-
-                        let attachedCount = 0;
-                        for (const path of paths) {
-                            const sizeBefore = useSessionStore.getState().attachedFiles.length;
-                            try {
-                                const normalizedPath = normalizeDroppedPath(path);
-                                const fileName = normalizedPath.split(/[\\/]/).pop() || normalizedPath;
-                                let file: File;
-
-                                // In desktop shell on remote origin, local file paths are not readable via /api/fs/raw.
-                                // Read bytes from local machine via Tauri command.
-                                if (isTauriShell() && !isDesktopLocalOriginActive()) {
-                                    const { invoke } = await import('@tauri-apps/api/core');
-                                    const result = await invoke<{ mime: string; base64: string }>('desktop_read_file', { path: normalizedPath });
-                                    const byteCharacters = atob(result.base64);
-                                    const byteNumbers = new Array(byteCharacters.length);
-                                    for (let i = 0; i < byteCharacters.length; i++) {
-                                        byteNumbers[i] = byteCharacters.charCodeAt(i);
-                                    }
-                                    const byteArray = new Uint8Array(byteNumbers);
-                                    const blob = new Blob([byteArray], { type: result.mime || 'application/octet-stream' });
-                                    file = new File([blob], fileName, { type: result.mime || 'application/octet-stream' });
-                                } else {
-                                    const response = await fetch(`/api/fs/raw?path=${encodeURIComponent(normalizedPath)}`);
-                                    if (!response.ok) {
-                                        throw new Error(`Failed to read dropped file (${response.status})`);
-                                    }
-                                    const blob = await response.blob();
-                                    file = new File([blob], fileName, { type: blob.type || 'application/octet-stream' });
-                                }
-
-                                await addAttachedFile(file);
-                                const sizeAfter = useSessionStore.getState().attachedFiles.length;
-                                if (sizeAfter > sizeBefore) attachedCount++;
-                            } catch (error) {
-                                console.error('Failed to attach dropped file:', path, error);
-                                toast.error(`Failed to attach ${path.split(/[\\/]/).pop() || 'file'}`);
-                            }
-                        }
-                        if (attachedCount > 0) {
-                            toast.success(`Attached ${attachedCount} file${attachedCount > 1 ? 's' : ''}`);
-                        }
+                        void attachDroppedPaths(paths);
                     }
-                });
+                );
+                unlisteners.push(removeDropListener);
 
                 if (cancelled) {
-                    removeListener();
-                    return;
+                    unlisteners.splice(0).forEach((unlisten) => unlisten());
                 }
-                unlisten = removeListener;
             } catch (error) {
                 if (!cancelled) {
                     console.warn('Failed to register Tauri drag-drop listener:', error);
@@ -1642,7 +1642,7 @@ export const ChatInput: React.FC<ChatInputProps> = ({ onOpenSettings, scrollToBo
 
         return () => {
             cancelled = true;
-            if (unlisten) unlisten();
+            unlisteners.splice(0).forEach((unlisten) => unlisten());
         };
     }, [addAttachedFile, normalizeDroppedPath]);
 
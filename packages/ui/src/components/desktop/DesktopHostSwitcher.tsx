@@ -33,9 +33,10 @@ import {
 } from '@remixicon/react';
 import { cn } from '@/lib/utils';
 import { toast } from '@/components/ui';
-import { isTauriShell, isDesktopShell } from '@/lib/desktop';
+import { isTauriShell, isDesktopShell, isDesktopLocalOnly } from '@/lib/desktop';
 import {
   desktopHostProbe,
+  desktopHostProbeAll,
   desktopHostsGet,
   desktopHostsSet,
   desktopOpenNewWindowAtUrl,
@@ -48,6 +49,7 @@ const LOCAL_HOST_ID = 'local';
 type HostStatus = {
   status: HostProbeResult['status'];
   latencyMs: number;
+  reason?: HostProbeResult['reason'];
 };
 
 const normalizeHostUrl = (raw: string): string | null => {
@@ -105,6 +107,14 @@ const statusLabel = (status: HostProbeResult['status'] | null): string => {
   return 'Unknown';
 };
 
+const reasonLabel = (reason: HostProbeResult['reason']): string => {
+  if (reason === 'dns') return "can't resolve host";
+  if (reason === 'refused') return 'connection refused';
+  if (reason === 'tls') return 'certificate error';
+  if (reason === 'timeout') return 'timed out';
+  return '';
+};
+
 const statusIcon = (status: HostProbeResult['status'] | null) => {
   if (status === 'ok') return <RiCheckLine className="h-4 w-4" />;
   if (status === 'auth') return <RiShieldKeyholeLine className="h-4 w-4" />;
@@ -227,19 +237,13 @@ export function DesktopHostSwitcherDialog({
     if (!isTauriShell()) return;
     setIsProbing(true);
     try {
-      const results = await Promise.all(
-        hosts.map(async (h) => {
-          const url = normalizeHostUrl(h.url);
-          if (!url) {
-            return [h.id, { status: 'unreachable' as const, latencyMs: 0 } satisfies HostStatus] as const;
-          }
-          const res = await desktopHostProbe(url).catch((): HostProbeResult => ({ status: 'unreachable', latencyMs: 0 }));
-          return [h.id, { status: res.status, latencyMs: res.latencyMs } satisfies HostStatus] as const;
-        })
-      );
+      const results = await desktopHostProbeAll().catch((): Record<string, HostProbeResult> => ({}));
       const next: Record<string, HostStatus> = {};
-      for (const [id, val] of results) {
-        next[id] = val;
+      for (const h of hosts) {
+        const res = results[h.id];
+        next[h.id] = res
+          ? { status: res.status, latencyMs: res.latencyMs, reason: res.reason }
+          : { status: 'unreachable', latencyMs: 0 };
       }
       setStatusById(next);
     } finally {
@@ -275,7 +279,7 @@ export function DesktopHostSwitcherDialog({
       const probe = await desktopHostProbe(origin).catch((): HostProbeResult => ({ status: 'unreachable', latencyMs: 0 }));
       setStatusById((prev) => ({
         ...prev,
-        [host.id]: { status: probe.status, latencyMs: probe.latencyMs },
+        [host.id]: { status: probe.status, latencyMs: probe.latencyMs, reason: probe.reason },
       }));
 
       if (probe.status === 'unreachable') {
@@ -368,7 +372,7 @@ export function DesktopHostSwitcherDialog({
     });
   }, []);
 
-  if (!isDesktopShell()) {
+  if (!isDesktopShell() || isDesktopLocalOnly()) {
     return null;
   }
 
@@ -489,6 +493,7 @@ export function DesktopHostSwitcherDialog({
                             <span>
                               {statusLabel(status?.status ?? null)}
                               {status?.status === 'ok' && typeof status.latencyMs === 'number' ? ` · ${Math.max(0, Math.round(status.latencyMs))}ms ping` : ''}
+                              {status?.status === 'unreachable' && reasonLabel(status.reason) ? ` · ${reasonLabel(status.reason)}` : ''}
                             </span>
                           </span>
                         </div>
@@ -761,7 +766,7 @@ export function DesktopHostSwitcherButton({ headerIconButtonClass }: DesktopHost
     };
   }, []);
 
-  if (!isDesktopShell()) {
+  if (!isDesktopShell() || isDesktopLocalOnly()) {
     return null;
   }
 
@@ -827,7 +832,7 @@ export function DesktopHostSwitcherButton({ headerIconButtonClass }: DesktopHost
 export function DesktopHostSwitcherInline() {
   const [open, setOpen] = React.useState(false);
 
-  if (!isDesktopShell()) {
+  if (!isDesktopShell() || isDesktopLocalOnly()) {
     return null;
   }
 
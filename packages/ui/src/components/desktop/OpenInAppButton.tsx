@@ -109,6 +109,7 @@ export const OpenInAppButton = ({ directory, className }: OpenInAppButtonProps)
   const [availableApps, setAvailableApps] = React.useState<OpenInAppOption[]>(getAlwaysAvailableApps);
   const [hasLoadedApps, setHasLoadedApps] = React.useState(false);
   const [isCacheStale, setIsCacheStale] = React.useState(false);
+  const [isTruncated, setIsTruncated] = React.useState(false);
   const [isScanning, setIsScanning] = React.useState(false);
   const isMountedRef = React.useRef(true);
   const isLoadingRef = React.useRef(false);
@@ -193,9 +194,11 @@ export const OpenInAppButton = ({ directory, className }: OpenInAppButtonProps)
         success,
         hasCache,
         isCacheStale: nextCacheStale,
+        isTruncated: nextTruncated,
       } = await fetchDesktopInstalledApps(appNames, force);
       if (!isMountedRef.current) return;
       setIsCacheStale(hasCache ? nextCacheStale : false);
+      setIsTruncated(hasCache ? nextTruncated : false);
       applyInstalledApps(installed);
       if (success) {
         if (!hasCache && installed.length === 0 && retryAttemptRef.current < 3) {
@@ -238,13 +241,14 @@ export const OpenInAppButton = ({ directory, className }: OpenInAppButtonProps)
     };
     window.addEventListener('openchamber:app-ready', handler);
     const updateHandler = (event: Event) => {
-      const detail = (event as CustomEvent<InstalledDesktopAppInfo[]>).detail;
-      if (Array.isArray(detail)) {
+      const detail = (event as CustomEvent<{ apps?: InstalledDesktopAppInfo[]; truncated?: boolean }>).detail;
+      if (detail && Array.isArray(detail.apps)) {
         retryAttemptRef.current = 3;
         keepScanningRef.current = false;
         setIsScanning(false);
         setIsCacheStale(false);
-        applyInstalledApps(detail);
+        setIsTruncated(detail.truncated === true);
+        applyInstalledApps(detail.apps);
       }
     };
     window.addEventListener('openchamber:installed-apps-updated', updateHandler);
@@ -393,7 +397,7 @@ export const OpenInAppButton = ({ directory, className }: OpenInAppButtonProps)
               ) : null}
             </DropdownMenuItem>
           ))}
-          {isCacheStale ? (
+          {isCacheStale || isTruncated ? (
             <DropdownMenuItem
               className="flex items-center gap-2"
               onClick={() => void loadInstalledApps(true)}
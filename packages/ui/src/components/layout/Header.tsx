@@ -54,7 +54,7 @@ import type { GitHubAuthStatus } from '@/lib/api/types';
 import type { SessionContextUsage } from '@/stores/types/sessionTypes';
 import { DesktopHostSwitcherDialog } from '@/components/desktop/DesktopHostSwitcher';
 import { OpenInAppButton } from '@/components/desktop/OpenInAppButton';
-import { isDesktopLocalOriginActive, isDesktopShell, isTauriShell, isVSCodeRuntime } from '@/lib/desktop';
+import { isDesktopLocalOnly, isDesktopLocalOriginActive, isDesktopShell, isTauriShell, isVSCodeRuntime } from '@/lib/desktop';
 import { sessionEvents } from '@/lib/sessionEvents';
 import { desktopHostsGet } from '@/lib/desktopHosts';
 import { ProjectEditDialog } from '@/components/layout/ProjectEditDialog';
@@ -268,14 +268,15 @@ export const Header: React.FC = () => {
   const [isDesktopServicesOpen, setIsDesktopServicesOpen] = React.useState(false);
   const [isUsageRefreshSpinning, setIsUsageRefreshSpinning] = React.useState(false);
   const [currentInstanceLabel, setCurrentInstanceLabel] = React.useState('Local');
+  const showInstanceTab = isDesktopApp && !isDesktopLocalOnly();
   const [desktopServicesTab, setDesktopServicesTab] = React.useState<'instance' | 'usage' | 'mcp'>(
-    isDesktopApp ? 'instance' : 'usage'
+    showInstanceTab ? 'instance' : 'usage'
   );
   useEffect(() => {
-    if (!isDesktopApp && desktopServicesTab === 'instance') {
+    if (!showInstanceTab && desktopServicesTab === 'instance') {
       setDesktopServicesTab('usage');
     }
-  }, [desktopServicesTab, isDesktopApp]);
+  }, [desktopServicesTab, showInstanceTab]);
 
   // --- Project tabs state (desktop, non-vscode only) ---
   const isVSCode = React.useMemo(() => isVSCodeRuntime(), []);
@@ -495,6 +496,50 @@ export const Header: React.FC = () => {
       });
   }, [addProject, tauriIpcAvailable]);
 
+  // Folders dropped onto the desktop window are added as projects (the last one is activated).
+  React.useEffect(() => {
+    if (!tauriIpcAvailable) return;
+    let cancelled = false;
+    let unlisten: (() => void) | null = null;
+
+    void (async () => {
+      try {
+        const { getCurrentWebviewWindow } = await import('@tauri-apps/api/webviewWindow');
+        const removeListener = await getCurrentWebviewWindow().listen<{ paths?: unknown }>(
+          'openchamber:directories-dropped',
+          (event) => {
+            if (!isDesktopLocalOriginActive()) {
+              toast.error('Folders can only be added as projects on the local instance');
+              return;
+            }
+            const paths = Array.isArray(event.payload?.paths)
+              ? event.payload.paths.filter((p): p is string => typeof p === 'string')
+              : [];
+            for (const path of paths) {
+              if (!addProject(path)) {
+                toast.error('Failed to add project', { description: path });
+              }
+            }
+          }
+        );
+        if (cancelled) {
+          removeListener();
+          return;
+        }
+        unlisten = removeListener;
+      } catch (error) {
+        if (!cancelled) {
+          console.warn('Failed to register folder drop listener:', error);
+        }
+      }
+    })();
+
+    return () => {
+      cancelled = true;
+      unlisten?.();
+    };
+  }, [addProject, tauriIpcAvailable]);
+
   const updateProjectMeta = useProjectsStore((state) => state.updateProjectMeta);
 
   const handleOpenProjectEdit = React.useCallback((projectId: string) => {
@@ -1013,7 +1058,7 @@ export const Header: React.FC = () => {
 
   const servicesTabs = React.useMemo(() => {
     const base: Array<{ value: 'instance' | 'usage' | 'mcp'; label: string; icon: RemixiconComponentType }> = [];
-    if (isDesktopApp) {
+    if (showInstanceTab) {
       base.push({ value: 'instance', label: 'Instance', icon: RiServerLine });
     }
     base.push(
@@ -1021,7 +1066,7 @@ export const Header: React.FC = () => {
       { value: 'mcp', label: 'MCP', icon: RiCommandLine }
     );
     return base;
-  }, [isDesktopApp]);
+  }, [showInstanceTab]);
 
   const quotaDisplayTabs = React.useMemo(() => {
     return [
@@ -1664,7 +1709,7 @@ export const Header: React.FC = () => {
                 />
               </div>
 
-              {isDesktopApp && desktopServicesTab === 'instance' && (
+              {showInstanceTab && desktopServicesTab === 'instance' && (
                 <DesktopHostSwitcherDialog
                   embedded
                   open={isDesktopServicesOpen && desktopServicesTab === 'instance'}
@@ -166,12 +166,21 @@ export const isDesktopLocalOriginActive = (): boolean => {
 // (Remote pages can temporarily lose window.__TAURI__ if URL doesn't match remote allowlist.)
 export const isDesktopShell = (): boolean => {
   if (typeof window === 'undefined') return false;
+  if (window.__OPENCHAMBER_DESKTOP__ === true) {
+    return true;
+  }
   if (typeof window.__OPENCHAMBER_LOCAL_ORIGIN__ === 'string' && window.__OPENCHAMBER_LOCAL_ORIGIN__.length > 0) {
     return true;
   }
   return isTauriShell();
 };
 
+// Local-only mode: windows always target the bundled server, so host switching is hidden.
+export const isDesktopLocalOnly = (): boolean => {
+  if (typeof window === 'undefined') return false;
+  return window.__OPENCHAMBER_LOCAL_ONLY__ === true;
+};
+
 export const isVSCodeRuntime = (): boolean => {
   if (typeof window === "undefined") return false;
   const apis = (window as { __OPENCHAMBER_RUNTIME_APIS__?: { runtime?: { isVSCode?: boolean } } }).__OPENCHAMBER_RUNTIME_APIS__;
@@ -291,13 +300,16 @@ export const downloadDesktopUpdate = async (
   let unlisten: null | (() => void | Promise<void>) = null;
   let downloaded = 0;
   let total: number | undefined;
+  // Progress events are broadcast to every window; only follow our own install.
+  const installId = `install-${Date.now()}-${Math.random().toString(36).slice(2, 10)}`;
 
   try {
     if (typeof onProgress === 'function' && tauri?.event?.listen) {
       unlisten = await tauri.event.listen('openchamber:update-progress', (evt) => {
         const payload = evt?.payload;
         if (!payload || typeof payload !== 'object') return;
-        const data = payload as { event?: unknown; data?: unknown };
+        const data = payload as { installId?: unknown; event?: unknown; data?: unknown };
+        if (typeof data.installId === 'string' && data.installId !== installId) return;
         const eventName = typeof data.event === 'string' ? data.event : null;
         const eventData = data.data && typeof data.data === 'object' ? (data.data as Record<string, unknown>) : null;
 
@@ -323,7 +335,7 @@ export const downloadDesktopUpdate = async (
       });
     }
 
-    await tauri?.core?.invoke?.('desktop_download_and_install_update');
+    await tauri?.core?.invoke?.('desktop_download_and_install_update', { installId });
     return true;
   } catch (error) {
     console.warn('Failed to download update (tauri)', error);
@@ -357,20 +369,51 @@ export const restartToApplyUpdate = async (): Promise<boolean> => {
   }
 };
 
-export const openDesktopPath = async (path: string, app?: string | null): Promise<boolean> => {
+// Tell the shell this window has rendered, so a window kept hidden until ready is shown.
+export const notifyDesktopWindowReady = async (): Promise<void> => {
+  if (!isTauriShell()) {
+    return;
+  }
+
+  try {
+    const tauri = (window as unknown as { __TAURI__?: TauriGlobal }).__TAURI__;
+    await tauri?.core?.invoke?.('desktop_window_ready');
+  } catch (error) {
+    console.warn('Failed to report desktop window ready', error);
+  }
+};
+
+// Report this window's active project so the shell can key saved window state by it.
+export const setDesktopWindowProject = async (path: string | null): Promise<void> => {
+  if (!isTauriShell()) {
+    return;
+  }
+
+  try {
+    const tauri = (window as unknown as { __TAURI__?: TauriGlobal }).__TAURI__;
+    await tauri?.core?.invoke?.('desktop_set_window_project', { path });
+  } catch (error) {
+    console.warn('Failed to report desktop window project', error);
+  }
+};
+
+// Several paths are opened by one `open` call, so editors put them in one window.
+export const openDesktopPath = async (path: string | string[], app?: string | null): Promise<boolean> => {
   if (!isTauriShell() || !isDesktopLocalOriginActive()) {
     return false;
   }
 
-  const trimmed = path?.trim();
-  if (!trimmed) {
+  const trimmed = (Array.isArray(path) ? path : [path])
+    .map((value) => (typeof value === 'string' ? value.trim() : ''))
+    .filter((value) => value.length > 0);
+  if (trimmed.length === 0) {
     return false;
   }
 
   try {
     const tauri = (window as unknown as { __TAURI__?: TauriGlobal }).__TAURI__;
     await tauri?.core?.invoke?.('desktop_open_path', {
-      path: trimmed,
+      path: trimmed.length === 1 ? trimmed[0] : trimmed,
       app: typeof app === 'string' && app.trim().length > 0 ? app.trim() : undefined,
     });
     return true;
@@ -444,6 +487,8 @@ export type FetchDesktopInstalledAppsResult = {
   success: boolean;
   hasCache: boolean;
   isCacheStale: boolean;
+  /** The list came from a scan that hit its deadline and may be missing apps. */
+  isTruncated: boolean;
 };
 
 export const fetchDesktopInstalledApps = async (
@@ -451,12 +496,12 @@ export const fetchDesktopInstalledApps = async (
   force?: boolean
 ): Promise<FetchDesktopInstalledAppsResult> => {
   if (!isTauriShell() || !isDesktopLocalOriginActive()) {
-    return { apps: [], success: false, hasCache: false, isCacheStale: false };
+    return { apps: [], success: false, hasCache: false, isCacheStale: false, isTruncated: false };
   }
 
   const candidate = Array.isArray(apps) ? apps.filter((value) => typeof value === 'string') : [];
   if (candidate.length === 0) {
-    return { apps: [], success: true, hasCache: false, isCacheStale: false };
+    return { apps: [], success: true, hasCache: false, isCacheStale: false, isTruncated: false };
   }
 
   try {
@@ -466,11 +511,11 @@ export const fetchDesktopInstalledApps = async (
       force: force === true ? true : undefined,
     });
     if (!result || typeof result !== 'object') {
-      return { apps: [], success: false, hasCache: false, isCacheStale: false };
+      return { apps: [], success: false, hasCache: false, isCacheStale: false, isTruncated: false };
     }
-    const payload = result as { apps?: unknown; hasCache?: unknown; isCacheStale?: unknown };
+    const payload = result as { apps?: unknown; hasCache?: unknown; isCacheStale?: unknown; isTruncated?: unknown };
     if (!Array.isArray(payload.apps)) {
-      return { apps: [], success: false, hasCache: false, isCacheStale: false };
+      return { apps: [], success: false, hasCache: false, isCacheStale: false, isTruncated: false };
     }
     const installedApps = payload.apps
       .filter((entry) => entry && typeof entry === 'object')
@@ -487,9 +532,10 @@ export const fetchDesktopInstalledApps = async (
       success: true,
       hasCache: payload.hasCache === true,
       isCacheStale: payload.isCacheStale === true,
+      isTruncated: payload.isTruncated === true,
     };
   } catch (error) {
     console.warn('Failed to fetch installed apps (tauri)', error);
-    return { apps: [], success: false, hasCache: false, isCacheStale: false };
+    return { apps: [], success: false, hasCache: false, isCacheStale: false, isTruncated: false };
   }
 };
@@ -12,6 +12,8 @@ export type DesktopHost = {
   id: string;
   label: string;
   url: string;
+  /** Power-user JS injected into windows opened at this host. */
+  initScript?: string;
 };
 
 export type DesktopHostsConfig = {
@@ -19,9 +21,12 @@ export type DesktopHostsConfig = {
   defaultHostId: string | null;
 };
 
+export type HostProbeFailureReason = 'dns' | 'refused' | 'tls' | 'timeout' | 'other';
+
 export type HostProbeResult = {
   status: 'ok' | 'auth' | 'unreachable';
   latencyMs: number;
+  reason?: HostProbeFailureReason;
 };
 
 const isRecord = (value: unknown): value is Record<string, unknown> => {
@@ -44,7 +49,8 @@ const parseHost = (value: unknown): DesktopHost | null => {
   const label = readString(value, 'label');
   const url = readString(value, 'url');
   if (!id || !label || !url) return null;
-  return { id, label, url };
+  const initScript = readString(value, 'initScript');
+  return initScript ? { id, label, url, initScript } : { id, label, url };
 };
 
 const getInvoke = (): TauriInvoke | null => {
@@ -88,13 +94,7 @@ export const desktopHostsSet = async (config: DesktopHostsConfig): Promise<void>
   });
 };
 
-export const desktopHostProbe = async (url: string): Promise<HostProbeResult> => {
-  const invoke = getInvoke();
-  if (!invoke) {
-    return { status: 'unreachable', latencyMs: 0 };
-  }
-
-  const raw = await invoke('desktop_host_probe', { url });
+const parseHostProbeResult = (raw: unknown): HostProbeResult => {
   if (!isRecord(raw)) {
     return { status: 'unreachable', latencyMs: 0 };
   }
@@ -106,7 +106,37 @@ export const desktopHostProbe = async (url: string): Promise<HostProbeResult> =>
       : 'unreachable';
 
   const latencyMs = readNumber(raw, 'latencyMs') ?? readNumber(raw, 'latency_ms') ?? 0;
-  return { status, latencyMs };
+  const rawReason = raw.reason;
+  const reason: HostProbeFailureReason | undefined =
+    rawReason === 'dns' || rawReason === 'refused' || rawReason === 'tls' || rawReason === 'timeout' || rawReason === 'other'
+      ? rawReason
+      : undefined;
+  return { status, latencyMs, reason };
+};
+
+export const desktopHostProbe = async (url: string): Promise<HostProbeResult> => {
+  const invoke = getInvoke();
+  if (!invoke) {
+    return { status: 'unreachable', latencyMs: 0 };
+  }
+
+  const raw = await invoke('desktop_host_probe', { url });
+  return parseHostProbeResult(raw);
+};
+
+/** Probe local plus every configured host in one concurrent call, keyed by host id. */
+export const desktopHostProbeAll = async (): Promise<Record<string, HostProbeResult>> => {
+  const invoke = getInvoke();
+  if (!invoke) return {};
+
+  const raw = await invoke('desktop_host_probe_all');
+  if (!isRecord(raw)) return {};
+
+  const results: Record<string, HostProbeResult> = {};
+  for (const [id, value] of Object.entries(raw)) {
+    results[id] = parseHostProbeResult(value);
+  }
+  return results;
 };
 
 export const desktopOpenNewWindowAtUrl = async (url: string): Promise<void> => {
@@ -1,4 +1,4 @@
-import { StrictMode } from 'react'
+import { StrictMode, useEffect } from 'react'
 import { createRoot } from 'react-dom/client'
 import './styles/fonts'
 import './index.css'
@@ -12,6 +12,7 @@ import { startAppearanceAutoSave } from './lib/appearanceAutoSave'
 import { applyPersistedDirectoryPreferences } from './lib/directoryPersistence'
 import { startTypographyWatcher } from './lib/typographyWatcher'
 import { startModelPrefsAutoSave } from './lib/modelPrefsAutoSave'
+import { notifyDesktopWindowReady } from './lib/desktop'
 import type { RuntimeAPIs } from './lib/api/types'
 
 declare global {
@@ -83,6 +84,15 @@ if (typeof window !== 'undefined') {
 
 }
 
+// Reports readiness from an effect, which runs after the first commit. Effects are
+// flushed by React's scheduler rather than rAF, which WebKit throttles in hidden webviews.
+function DesktopWindowReadySignal() {
+  useEffect(() => {
+    void notifyDesktopWindowReady();
+  }, []);
+  return null;
+}
+
 const rootElement = document.getElementById('root');
 if (!rootElement) {
   throw new Error('Root element not found');
@@ -90,6 +100,7 @@ if (!rootElement) {
 
 createRoot(rootElement).render(
   <StrictMode>
+    <DesktopWindowReadySignal />
     <ThemeSystemProvider>
       <ThemeProvider>
         <SessionAuthGate>
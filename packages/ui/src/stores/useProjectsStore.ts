@@ -2,7 +2,7 @@ import { create } from 'zustand';
 import { devtools } from 'zustand/middleware';
 import { opencodeClient } from '@/lib/opencode/client';
 import type { ProjectEntry } from '@/lib/api/types';
-import type { DesktopSettings } from '@/lib/desktop';
+import { setDesktopWindowProject, type DesktopSettings } from '@/lib/desktop';
 import { updateDesktopSettings } from '@/lib/persistence';
 import { getSafeStorage } from './utils/safeStorage';
 import { useDirectoryStore } from './useDirectoryStore';
@@ -463,6 +463,23 @@ export const useProjectsStore = create<ProjectsStore>()(
 );
 
 if (typeof window !== 'undefined') {
+  // Keep the desktop shell's per-window project (part of the window identity) current.
+  let reportedProjectPath: string | null | undefined;
+  const reportActiveProject = () => {
+    const path = useProjectsStore.getState().getActiveProject()?.path ?? null;
+    if (path === reportedProjectPath) {
+      return;
+    }
+    reportedProjectPath = path;
+    void setDesktopWindowProject(path);
+  };
+  reportActiveProject();
+  useProjectsStore.subscribe((state, prevState) => {
+    if (state.activeProjectId !== prevState.activeProjectId || state.projects !== prevState.projects) {
+      reportActiveProject();
+    }
+  });
+
   window.addEventListener('openchamber:settings-synced', (event: Event) => {
     const detail = (event as CustomEvent<DesktopSettings>).detail;
     if (detail && typeof detail === 'object') {
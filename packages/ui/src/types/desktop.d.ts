@@ -1,5 +1,10 @@
 declare global {
   interface Window {
+    __OPENCHAMBER_DESKTOP__?: boolean;
+    __OPENCHAMBER_DESKTOP_VERSION__?: string;
+    __OPENCHAMBER_DESKTOP_PLATFORM__?: string;
+    __OPENCHAMBER_UPDATER_DISABLED__?: boolean;
+    __OPENCHAMBER_LOCAL_ONLY__?: boolean;
     __OPENCHAMBER_HOME__?: string;
     __OPENCHAMBER_MACOS_MAJOR__?: number;
     __OPENCHAMBER_LOCAL_ORIGIN__?: string;